@@ -0,0 +1,317 @@
+//! Terminal animation of simulations that evolve a 2D state one step at a time
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Delays between steps that the speed controls move between, fastest first
+pub const DELAYS: [Duration; 10] = [
+    Duration::from_millis(0),
+    Duration::from_millis(1),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_millis(1000),
+];
+
+const HELP: &str = "space play/pause, n step, +/- speed, q quit";
+
+/// A state that evolves one step at a time and is drawn as lines of text
+pub trait Simulation: fmt::Display {
+    /// Advances one step, returning whether there is anything left to simulate
+    fn step(&mut self) -> bool;
+
+    /// The `(column, line)` of the rendering to keep in view when it doesn't fit the terminal
+    fn focus(&self) -> Option<(usize, usize)> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    TogglePause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Control {
+    pub fn from_key(key: KeyEvent) -> Option<Self> {
+        if key.kind == KeyEventKind::Release {
+            return None;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Control::Quit)
+            }
+            KeyCode::Char(' ') | KeyCode::Char('p') => Some(Control::TogglePause),
+            KeyCode::Char('n') | KeyCode::Char('.') | KeyCode::Right => Some(Control::Step),
+            KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => Some(Control::Faster),
+            KeyCode::Char('-') | KeyCode::Down => Some(Control::Slower),
+            KeyCode::Char('q') | KeyCode::Esc => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Playback state of an animation, independent of the terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub paused: bool,
+    pub finished: bool,
+    pub steps: u64,
+    speed: usize,
+}
+
+impl Player {
+    /// A playing player that uses the listed delay closest to `delay`
+    pub fn new(delay: Duration) -> Self {
+        let speed = (0..DELAYS.len())
+            .min_by_key(|&i| DELAYS[i].abs_diff(delay))
+            .unwrap();
+        Self {
+            paused: false,
+            finished: false,
+            steps: 0,
+            speed,
+        }
+    }
+
+    pub fn delay(&self) -> Duration {
+        DELAYS[self.speed]
+    }
+
+    /// Whether the player waits for a control instead of stepping after the delay
+    pub fn is_waiting(&self) -> bool {
+        self.paused || self.finished
+    }
+
+    /// Steps the simulation unless it has finished
+    pub fn advance<S: Simulation>(&mut self, simulation: &mut S) {
+        if !self.finished {
+            self.finished = !simulation.step();
+            self.steps += 1;
+        }
+    }
+
+    pub fn control<S: Simulation>(&mut self, control: Control, simulation: &mut S) {
+        match control {
+            Control::TogglePause => self.paused = !self.paused,
+            Control::Step => {
+                self.paused = true;
+                self.advance(simulation);
+            }
+            Control::Faster => self.speed = self.speed.saturating_sub(1),
+            Control::Slower => self.speed = (self.speed + 1).min(DELAYS.len() - 1),
+            Control::Quit => {}
+        }
+    }
+
+    pub fn status(&self) -> String {
+        let state = if self.finished {
+            "finished"
+        } else if self.paused {
+            "paused"
+        } else {
+            "playing"
+        };
+        format!(
+            "step {} | {} | {}ms per step | {}",
+            self.steps,
+            state,
+            self.delay().as_millis(),
+            HELP
+        )
+    }
+}
+
+/// The ANSI escape codes that redraw the terminal with the part of the rendering that fits in
+/// `(columns, rows)` and the player's status on the last row
+pub fn frame<S: Simulation>(
+    simulation: &S,
+    player: &Player,
+    (columns, rows): (usize, usize),
+) -> String {
+    let rendered = simulation.to_string();
+    let lines: Vec<_> = rendered.lines().collect();
+    let rows = rows.saturating_sub(1);
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let (focus_column, focus_line) = simulation.focus().unwrap_or((0, 0));
+    let left = window_start(focus_column, columns, width);
+    let top = window_start(focus_line, rows, lines.len());
+    // Move to the top left and clear what is left of each line from the previous frame
+    let mut frame = String::from("\x1b[H");
+    for line in lines.iter().skip(top).take(rows) {
+        frame.extend(line.chars().skip(left).take(columns));
+        frame.push_str("\x1b[K\r\n");
+    }
+    frame.push_str("\x1b[J");
+    frame.extend(player.status().chars().take(columns));
+    frame
+}
+
+/// First index of a window of `size` around `focus` that stays within `0..length` if it can
+fn window_start(focus: usize, size: usize, length: usize) -> usize {
+    focus
+        .saturating_sub(size / 2)
+        .min(length.saturating_sub(size))
+}
+
+/// Raw mode on the alternate screen with the cursor hidden, until dropped
+struct Terminal;
+
+impl Terminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush()?;
+        Ok(Terminal)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+enum Input {
+    Control(Control),
+    TimedOut,
+    /// Other keys and resizes, which only redraw
+    Other,
+}
+
+/// Waits up to `timeout`, or forever without one, for a key
+fn next_input(timeout: Option<Duration>) -> io::Result<Input> {
+    if let Some(timeout) = timeout {
+        if !event::poll(timeout)? {
+            return Ok(Input::TimedOut);
+        }
+    }
+    match event::read()? {
+        Event::Key(key) => Ok(Control::from_key(key).map_or(Input::Other, Input::Control)),
+        _ => Ok(Input::Other),
+    }
+}
+
+/// Plays a simulation in the terminal until it is quit, starting with `delay` between steps
+pub fn animate<S: Simulation>(simulation: &mut S, delay: Duration) -> io::Result<()> {
+    let _terminal = Terminal::enter()?;
+    let mut player = Player::new(delay);
+    let mut stdout = io::stdout();
+    loop {
+        let (columns, rows) = terminal::size()?;
+        let frame = frame(simulation, &player, (columns as usize, rows as usize));
+        stdout.write_all(frame.as_bytes())?;
+        stdout.flush()?;
+        let timeout = if player.is_waiting() {
+            None
+        } else {
+            Some(player.delay())
+        };
+        match next_input(timeout)? {
+            Input::TimedOut => player.advance(simulation),
+            Input::Control(Control::Quit) => return Ok(()),
+            Input::Control(control) => player.control(control, simulation),
+            Input::Other => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A dot that moves right along a line until the end
+    struct Dot {
+        position: usize,
+        length: usize,
+    }
+
+    impl fmt::Display for Dot {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            for i in 0..self.length {
+                write!(f, "{}", if i == self.position { '#' } else { '.' })?;
+            }
+            writeln!(f)
+        }
+    }
+
+    impl Simulation for Dot {
+        fn step(&mut self) -> bool {
+            self.position += 1;
+            self.position + 1 < self.length
+        }
+
+        fn focus(&self) -> Option<(usize, usize)> {
+            Some((self.position, 0))
+        }
+    }
+
+    #[test]
+    fn it_controls_playback() {
+        let mut dot = Dot {
+            position: 0,
+            length: 3,
+        };
+        let mut player = Player::new(Duration::from_millis(90));
+        assert_eq!(player.delay(), Duration::from_millis(100));
+        player.control(Control::Faster, &mut dot);
+        assert_eq!(player.delay(), Duration::from_millis(50));
+        player.advance(&mut dot);
+        assert_eq!((dot.position, player.is_waiting()), (1, false));
+        player.control(Control::Step, &mut dot);
+        assert!(player.paused && player.finished);
+        player.advance(&mut dot);
+        assert_eq!((dot.position, player.steps), (2, 2));
+        assert_eq!(
+            player.status(),
+            "step 2 | finished | 50ms per step | space play/pause, n step, +/- speed, q quit"
+        );
+        for _ in 0..20 {
+            player.control(Control::Slower, &mut dot);
+        }
+        assert_eq!(player.delay(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn it_maps_keys_to_controls() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(
+            Control::from_key(key(KeyCode::Char(' '))),
+            Some(Control::TogglePause)
+        );
+        assert_eq!(Control::from_key(key(KeyCode::Right)), Some(Control::Step));
+        assert_eq!(Control::from_key(key(KeyCode::Char('x'))), None);
+        assert_eq!(
+            Control::from_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Control::Quit)
+        );
+    }
+
+    #[test]
+    fn it_draws_the_window_around_the_focus() {
+        let dot = Dot {
+            position: 6,
+            length: 10,
+        };
+        let player = Player::new(Duration::from_millis(100));
+        assert_eq!(
+            frame(&dot, &player, (4, 2)),
+            "\x1b[H..#.\x1b[K\r\n\x1b[Jstep"
+        );
+        assert_eq!(window_start(0, 4, 10), 0);
+        assert_eq!(window_start(9, 4, 10), 6);
+        assert_eq!(window_start(3, 20, 10), 0);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+pub const ANSWERS_FILE_NAME: &str = "answers.toml";
+
+/// Known answers for a puzzle input
+///
+/// Stored in an `answers.toml` next to the input, either part may be missing:
+///
+/// ```toml
+/// part_one = "427"
+/// part_two = "341"
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+/// Outcome of checking a solved part against its known answer
+#[derive(Debug, PartialEq)]
+pub enum Verification {
+    Correct,
+    Incorrect { expected: String, actual: String },
+    Unknown { actual: String },
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self> {
+        let value: Value = s.parse()?;
+        let table = value.as_table().ok_or("Answers must be a table")?;
+        let get = |key: &str| -> Result<Option<String>> {
+            match table.get(key) {
+                None => Ok(None),
+                Some(Value::String(s)) => Ok(Some(s.clone())),
+                Some(Value::Integer(n)) => Ok(Some(n.to_string())),
+                Some(v) => Err(Box::from(format!("Invalid answer for {}: {}", key, v))),
+            }
+        };
+        Ok(Self {
+            part_one: get("part_one")?,
+            part_two: get("part_two")?,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let s = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read answers {}: {}", path.display(), e))?;
+        Self::parse(&s).map_err(|e| Box::from(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Compares an answer with the known one, ignoring surrounding whitespace
+    pub fn verify(&self, part: u8, actual: String) -> Verification {
+        match self.get(part) {
+            Some(expected) if expected.trim() == actual.trim() => Verification::Correct,
+            Some(expected) => Verification::Incorrect {
+                expected: String::from(expected),
+                actual,
+            },
+            None => Verification::Unknown { actual },
+        }
+    }
+}
+
+/// Path of the answers file for the given input file
+pub fn answers_path(input_path: &Path) -> PathBuf {
+    input_path.with_file_name(ANSWERS_FILE_NAME)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_parses_answers() {
+        let answers = Answers::parse("part_one = \"82,104\"\npart_two = 10391\n").unwrap();
+        assert_eq!(answers.get(1), Some("82,104"));
+        assert_eq!(answers.get(2), Some("10391"));
+        assert_eq!(Answers::parse("").unwrap(), Answers::default());
+        assert!(Answers::parse("part_one = [1]").is_err());
+    }
+
+    #[test]
+    fn it_verifies_answers() {
+        let answers = Answers::parse("part_one = '''\n#..#\n'''").unwrap();
+        assert_eq!(
+            answers.verify(1, String::from("\n#..#\n\n")),
+            Verification::Correct
+        );
+        assert_eq!(
+            answers.verify(1, String::from("#...")),
+            Verification::Incorrect {
+                expected: String::from("#..#\n"),
+                actual: String::from("#..."),
+            }
+        );
+        assert_eq!(
+            answers.verify(2, String::from("1")),
+            Verification::Unknown {
+                actual: String::from("1")
+            }
+        );
+    }
+
+    #[test]
+    fn it_finds_answers_next_to_the_input() {
+        assert_eq!(
+            answers_path(Path::new("solutions/day-01/input")),
+            PathBuf::from("solutions/day-01/answers.toml")
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use animation::Simulation;
+use common::Solution;
+use elfcode::Program;
+use raster::{Draw, FrameWriter, Image};
+use std::path::PathBuf;
+use std::time::Duration;
+
+mod answers;
+mod samples;
+mod scaffold;
+
+pub use crate::answers::{answers_path, Answers, Verification};
+pub use crate::samples::{find_samples, sample_answers_path, Sample, SAMPLE_ANSWERS_SUFFIX};
+pub use crate::scaffold::scaffold;
+pub use common::{PartResult, Result};
+
+/// Type erased entry points into a day's `Solution`
+pub struct Solver {
+    /// Parses the input and solves the given part, returning the timed answer
+    pub solve: fn(&str, u8) -> Result<PartResult>,
+    /// Loads the input from a path, `-` for stdin or a literal value
+    pub load_input: fn(&str) -> Result<String>,
+}
+
+fn solver<S: Solution>() -> Solver {
+    Solver {
+        solve: common::solve_timed::<S>,
+        load_input: common::load_input::<S>,
+    }
+}
+
+pub const DAYS: u32 = 25;
+
+const ROOT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+const SOLUTIONS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../solutions");
+
+/// Returns the solver for the given day
+pub fn find_solver(day: u32) -> Option<Solver> {
+    let solver = match day {
+        1 => solver::<day_1::Day1>(),
+        2 => solver::<day_2::Day2>(),
+        3 => solver::<day_3::Day3>(),
+        4 => solver::<day_4::Day4>(),
+        5 => solver::<day_5::Day5>(),
+        6 => solver::<day_6::Day6>(),
+        7 => solver::<day_7::Day7>(),
+        8 => solver::<day_8::Day8>(),
+        9 => solver::<day_9::Day9>(),
+        10 => solver::<day_10::Day10>(),
+        11 => solver::<day_11::Day11>(),
+        12 => solver::<day_12::Day12>(),
+        13 => solver::<day_13::Day13>(),
+        14 => solver::<day_14::Day14>(),
+        15 => solver::<day_15::Day15>(),
+        16 => solver::<day_16::Day16>(),
+        17 => solver::<day_17::Day17>(),
+        18 => solver::<day_18::Day18>(),
+        19 => solver::<day_19::Day19>(),
+        20 => solver::<day_20::Day20>(),
+        21 => solver::<day_21::Day21>(),
+        22 => solver::<day_22::Day22>(),
+        23 => solver::<day_23::Day23>(),
+        24 => solver::<day_24::Day24>(),
+        25 => solver::<day_25::Day25>(),
+        _ => return None,
+    };
+    Some(solver)
+}
+
+/// Root of the workspace, which holds the solutions and the README
+pub fn root_dir() -> PathBuf {
+    PathBuf::from(ROOT_DIR)
+}
+
+/// Directory of the day's crate, which holds its input, answers and samples
+pub fn day_dir(day: u32) -> PathBuf {
+    PathBuf::from(SOLUTIONS_DIR).join(format!("day-{:02}", day))
+}
+
+/// Path of the puzzle input checked in next to the day's crate
+pub fn default_input_path(day: u32) -> PathBuf {
+    day_dir(day).join("input")
+}
+
+/// Solves the given part of a day, timing the parse and the solve separately
+pub fn solve(day: u32, part: u8, input: &str) -> Result<PartResult> {
+    let solver = find_solver(day).ok_or_else(|| format!("No solution for day {}", day))?;
+    (solver.solve)(input, part)
+}
+
+/// Loads the input for the given day from a path, `-` for stdin or a literal value
+pub fn load_input(day: u32, arg: &str) -> Result<String> {
+    let solver = find_solver(day).ok_or_else(|| format!("No solution for day {}", day))?;
+    (solver.load_input)(arg)
+}
+
+/// Plays the simulation of a day with a state that evolves over time in the terminal
+pub fn animate(day: u32, input: &str, delay: Duration) -> Result<()> {
+    match day {
+        10 => animation::animate(&mut day_10::Sky::new(day_10::parse_input(input)?), delay)?,
+        13 => animation::animate(&mut day_13::Map::from_input(input)?, delay)?,
+        15 => animation::animate(&mut day_15::World::from_input(input)?, delay)?,
+        17 => {
+            let world = day_17::World::from_input(input)?;
+            animation::animate(&mut day_17::WaterFlow::new(world), delay)?
+        }
+        18 => animation::animate(&mut day_18::World::from_input(input)?, delay)?,
+        _ => return Err(Box::from(format!("No animation for day {}", day))),
+    }
+    Ok(())
+}
+
+/// Days whose input is an ElfCode program
+pub const ELFCODE_DAYS: [u32; 3] = [16, 19, 21];
+
+/// The ElfCode program of a day
+#[derive(Debug, Clone)]
+pub enum ElfCode {
+    /// Day 16's test program with its op numbers decoded from the samples, which only has four
+    /// registers
+    Device(Program<u32, 4>),
+    Program(Program),
+}
+
+/// Checks that the day's input is an ElfCode program
+pub fn elfcode_day(day: u32) -> Result<u32> {
+    if ELFCODE_DAYS.contains(&day) {
+        Ok(day)
+    } else {
+        Err(Box::from(format!(
+            "Day {} is not an ElfCode program, only days 16, 19 and 21 are",
+            day
+        )))
+    }
+}
+
+/// Parses the ElfCode program of day 16, 19 or 21
+pub fn load_program(day: u32, input: &str) -> Result<ElfCode> {
+    match elfcode_day(day)? {
+        16 => Ok(ElfCode::Device(day_16::Device::from_input(input)?.program)),
+        _ => Ok(ElfCode::Program(input.parse()?)),
+    }
+}
+
+/// Draws the final state of a day with a grid too large to read as text
+pub fn draw(day: u32, input: &str) -> Result<Image> {
+    let image = match day {
+        6 => day_6::draw_areas(&day_6::parse_input(input)?),
+        17 => {
+            let mut world = day_17::World::from_input(input)?;
+            world.simulate_water();
+            world.draw()
+        }
+        18 => {
+            let mut world = day_18::World::from_input(input)?;
+            world.simulate(day_18::PART_ONE_MINUTES);
+            world.draw()
+        }
+        22 => {
+            let mut cave = day_22::Cave::from_input(input)?;
+            cave.find_path_to_target();
+            cave.draw()
+        }
+        _ => return Err(Box::from(format!("No image for day {}", day))),
+    };
+    Ok(image)
+}
+
+/// How often and how many frames of a simulation to write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameOptions {
+    /// Pixels per side of each tile
+    pub scale: usize,
+    /// Steps between frames
+    pub every: usize,
+    /// Most frames to write
+    pub limit: usize,
+}
+
+/// Writes the frames of a day's simulation from its initial state until it ends or the limit
+/// is reached
+pub fn write_frames(
+    day: u32,
+    input: &str,
+    frames: &mut FrameWriter,
+    options: FrameOptions,
+) -> Result<()> {
+    match day {
+        17 => {
+            let world = day_17::World::from_input(input)?;
+            record(&mut day_17::WaterFlow::new(world), frames, options)
+        }
+        18 => record(&mut day_18::World::from_input(input)?, frames, options),
+        _ => Err(Box::from(format!("No frames for day {}", day))),
+    }
+}
+
+fn record<S: Simulation + Draw>(
+    simulation: &mut S,
+    frames: &mut FrameWriter,
+    options: FrameOptions,
+) -> Result<()> {
+    frames.write(&simulation.draw().scaled(options.scale))?;
+    let mut running = true;
+    while running && frames.frames() < options.limit {
+        for _ in 0..options.every {
+            running = simulation.step();
+            if !running {
+                break;
+            }
+        }
+        frames.write(&simulation.draw().scaled(options.scale))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use raster::Format;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn it_finds_solvers_for_every_day() {
+        for day in 1..=DAYS {
+            assert!(find_solver(day).is_some(), "missing solver for day {}", day);
+        }
+        assert!(find_solver(0).is_none());
+        assert!(find_solver(DAYS + 1).is_none());
+    }
+
+    #[test]
+    fn it_solves_with_given_input() {
+        assert_eq!(solve(1, 1, "+1\n-2\n+3\n+1").unwrap().answer, "3");
+        assert_eq!(solve(1, 2, "+1\n-2\n+3\n+1").unwrap().answer, "2");
+        assert!(solve(1, 3, "+1").is_err());
+        assert!(solve(26, 1, "").is_err());
+    }
+
+    #[test]
+    fn it_loads_literal_input_for_days_11_and_14() {
+        assert_eq!(load_input(11, "5034").unwrap(), "5034");
+        assert_eq!(load_input(14, "330121").unwrap(), "330121");
+        assert!(load_input(1, "5034").is_err());
+    }
+
+    #[test]
+    fn it_loads_the_programs_of_elfcode_days() {
+        for &day in ELFCODE_DAYS.iter() {
+            let input = fs::read_to_string(default_input_path(day)).unwrap();
+            let program = load_program(day, &input).unwrap();
+            assert_eq!(matches!(program, ElfCode::Device(_)), day == 16);
+        }
+        assert_eq!(
+            load_program(18, ".#").unwrap_err().to_string(),
+            "Day 18 is not an ElfCode program, only days 16, 19 and 21 are"
+        );
+    }
+
+    #[test]
+    fn it_only_animates_days_with_a_simulation() {
+        assert!(animate(1, "+1", Duration::from_millis(100)).is_err());
+        assert!(animate(15, "#x#", Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    fn it_draws_images_and_frames() {
+        let input = fs::read_to_string(day_dir(17).join("sample-input")).unwrap();
+        let image = draw(17, &input).unwrap();
+        assert_eq!((image.width(), image.height()), (14, 14));
+        assert!(draw(1, "+1").is_err());
+
+        let dir = env::temp_dir().join(format!("aoc-frames-test-{}", std::process::id()));
+        let mut frames = FrameWriter::new(&dir, Format::Ppm).unwrap();
+        let options = FrameOptions {
+            scale: 2,
+            every: 5,
+            limit: 100,
+        };
+        write_frames(17, &input, &mut frames, options).unwrap();
+        let last_frame = fs::read(frames.frame_path(frames.frames())).unwrap();
+        let mut expected = vec![];
+        image.scaled(2).write_ppm(&mut expected).unwrap();
+        assert_eq!(last_frame, expected);
+        let mut frames = FrameWriter::new(&dir, Format::Ppm).unwrap();
+        let options = FrameOptions {
+            limit: 3,
+            ..options
+        };
+        write_frames(17, &input, &mut frames, options).unwrap();
+        assert_eq!(frames.frames(), 3);
+        assert!(write_frames(13, &input, &mut frames, options).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
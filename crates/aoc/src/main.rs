@@ -0,0 +1,848 @@
+use aoc::{
+    animate, answers_path, default_input_path, draw, elfcode_day, find_samples, load_input,
+    load_program, root_dir, scaffold, solve, write_frames, Answers, ElfCode, FrameOptions,
+    PartResult, Result, Verification, DAYS, SAMPLE_ANSWERS_SUFFIX,
+};
+use elfcode::{Debugger, Decompiled, Program, Tracer, Word};
+use raster::{Format as ImageFormat, FrameWriter};
+use serde_json::json;
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::iter::Peekable;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+const USAGE: &str = "USAGE: aoc [--log <filter>] <command>
+
+       aoc run <day> [--part <1|2>] [--input <path|->] [--format <text|json>]
+       aoc verify [<day>...]
+       aoc samples [<day>...]
+       aoc debug <day> [--input <path>]
+       aoc decompile <day> [--input <path>]
+       aoc profile <day> [--input <path>] [--steps <count>] [--set <register>=<value>]...
+                   [--trace <path>]
+       aoc animate <day> [--input <path>] [--delay <ms>]
+       aoc image <day> [--input <path>] [--output <path>] [--scale <pixels>]
+                 [--frames [--image-format <png|ppm>] [--every <steps>] [--limit <frames>]]
+       aoc new <day>
+
+--log             Log diagnostics to stderr, like --log debug for everything or
+                  --log day_24=trace,day_15=debug for some days, defaults to RUST_LOG
+run               Solve a day and print the answers
+verify            Check the answers for each day's input against the answers.toml next to it,
+                  every day is verified by default
+samples           Check each day's sample inputs against the <sample>.answers.toml next to
+                  them, every day is checked by default
+debug             Step through the ElfCode program of day 16, 19 or 21 in a debugger, enter
+                  help at its prompt for the commands
+decompile         Print the ElfCode program of day 16, 19 or 21 as pseudo-code with its jumps,
+                  blocks and loops
+profile           Run the ElfCode program of day 16, 19 or 21 instruction by instruction and
+                  print the lines it spends the most time on
+animate           Play the simulation of day 10, 13, 15, 17 or 18 in the terminal, space
+                  pauses, n steps, + and - change the speed and q quits
+image             Draw the final state of day 6, 17, 18 or 22 as a PNG or PPM image with a
+                  pixel per tile, or with --frames every state of day 17 or 18 as numbered
+                  images that ffmpeg can turn into a video
+new               Create the crate for a day with an empty input and sample-input, each with an
+                  answers file, and add it to the workspace, the runner and the README
+day               The day number to run (1-25)
+--part            Only run the given part, both parts are run by default
+--input           Path to the puzzle input or - for stdin, days 11 and 14 also take the value
+                  itself, defaults to solutions/day-<day>/input
+--format          Print only the answers as text, the default, or the answers with their parse
+                  and solve times as JSON
+--steps           Stop profiling after this many instructions, defaults to 10000000
+--set             Set a register before profiling, like --set 0=1 for day 19 part two
+--trace           Write the registers after each of the last 10000 instructions as CSV
+--delay           Milliseconds between the steps of an animation, defaults to 100
+--output          The .png or .ppm image to write, defaults to day-<day>.png, or the directory
+                  for the frames, defaults to day-<day>-frames
+--scale           Pixels per side of each tile, defaults to 1
+--frames          Write a frame for every state instead of an image of the final state
+--image-format    The format of the frames, png or ppm, defaults to png
+--every           Steps between frames, defaults to 1
+--limit           Most frames to write, defaults to 1000";
+
+const PROFILE_STEPS: u64 = 10_000_000;
+const TRACE_LENGTH: usize = 10_000;
+const HOT_SPOTS: usize = 15;
+const ANIMATION_DELAY_MS: u64 = 100;
+const FRAME_LIMIT: usize = 1000;
+
+#[derive(Debug, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// Parses the day and the `--input` that every command on a day's input takes, passing each
+/// other argument and the arguments after it to `parse_option`
+fn parse_day_args<I, F>(mut args: I, mut parse_option: F) -> Result<(u32, String)>
+where
+    I: Iterator<Item = String>,
+    F: FnMut(&str, &mut I) -> Result<()>,
+{
+    let day = args
+        .next()
+        .ok_or("Missing day")?
+        .parse()
+        .map_err(|_| "Day must be a number")?;
+    let mut input = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => input = Some(option_value(&arg, &mut args)?),
+            _ => parse_option(&arg, &mut args)?,
+        }
+    }
+    let input = input.unwrap_or_else(|| default_input_path(day).display().to_string());
+    Ok((day, input))
+}
+
+/// Takes the value that must follow an option
+fn option_value<I>(option: &str, args: &mut I) -> Result<String>
+where
+    I: Iterator<Item = String>,
+{
+    args.next()
+        .ok_or_else(|| Box::from(format!("Missing value for {}", option)))
+}
+
+fn unexpected_argument(arg: &str) -> Box<dyn Error> {
+    Box::from(format!("Unexpected argument: {}", arg))
+}
+
+#[derive(Debug, PartialEq)]
+struct RunOptions {
+    day: u32,
+    parts: Vec<u8>,
+    input: String,
+    format: Format,
+}
+
+fn parse_run_args<I>(args: I) -> Result<RunOptions>
+where
+    I: Iterator<Item = String>,
+{
+    let mut parts = vec![1, 2];
+    let mut format = Format::Text;
+    let (day, input) = parse_day_args(args, |arg, args| {
+        match arg {
+            "--part" => {
+                let part = option_value(arg, args)?;
+                match part.as_str() {
+                    "1" => parts = vec![1],
+                    "2" => parts = vec![2],
+                    _ => return Err(Box::from(format!("Invalid part: {}", part))),
+                }
+            }
+            "--format" => {
+                let value = option_value(arg, args)?;
+                match value.as_str() {
+                    "text" => format = Format::Text,
+                    "json" => format = Format::Json,
+                    _ => return Err(Box::from(format!("Invalid format: {}", value))),
+                }
+            }
+            _ => return Err(unexpected_argument(arg)),
+        }
+        Ok(())
+    })?;
+    Ok(RunOptions {
+        day,
+        parts,
+        input,
+        format,
+    })
+}
+
+fn run(options: &RunOptions) -> Result<()> {
+    let input = load_input(options.day, &options.input)?;
+    match options.format {
+        Format::Text => {
+            for &part in options.parts.iter() {
+                println!("{}", solve(options.day, part, &input)?.answer);
+            }
+        }
+        Format::Json => {
+            let results = options
+                .parts
+                .iter()
+                .map(|&part| solve(options.day, part, &input).map(|r| to_json(options.day, &r)))
+                .collect::<Result<Vec<_>>>()?;
+            println!("{}", serde_json::Value::Array(results));
+        }
+    }
+    Ok(())
+}
+
+fn to_json(day: u32, result: &PartResult) -> serde_json::Value {
+    json!({
+        "day": day,
+        "part": result.part,
+        "answer": result.answer,
+        "parse_time_ns": result.parse_time.as_nanos() as u64,
+        "solve_time_ns": result.solve_time.as_nanos() as u64,
+    })
+}
+
+fn parse_verify_args<I>(args: I) -> Result<Vec<u32>>
+where
+    I: Iterator<Item = String>,
+{
+    let days = args
+        .map(|arg| match arg.parse() {
+            Ok(day) if (1..=DAYS).contains(&day) => Ok(day),
+            _ => Err(Box::from(format!("Invalid day: {}", arg))),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if days.is_empty() {
+        Ok((1..=DAYS).collect())
+    } else {
+        Ok(days)
+    }
+}
+
+/// Verifies both parts of a day, returning whether every known answer matched
+fn verify_day(day: u32) -> Result<bool> {
+    let input_path = default_input_path(day);
+    let answers_path = answers_path(&input_path);
+    if !input_path.is_file() || !answers_path.is_file() {
+        println!("Day {:2}: skipped, no input and answers", day);
+        return Ok(true);
+    }
+    let answers = Answers::load(&answers_path)?;
+    let input = load_input(day, &input_path.display().to_string())?;
+    let mut all_correct = true;
+    for part in 1..=2 {
+        let actual = solve(day, part, &input)?.answer;
+        let label = format!("Day {:2} part {}", day, part);
+        all_correct &= report(&label, answers.verify(part, actual));
+    }
+    Ok(all_correct)
+}
+
+/// Prints the outcome of checking an answer, returning whether it wasn't a mismatch
+fn report(label: &str, verification: Verification) -> bool {
+    match verification {
+        Verification::Correct => println!("{}: ok", label),
+        Verification::Unknown { actual } => println!("{}: no known answer, got {}", label, actual),
+        Verification::Incorrect { expected, actual } => {
+            println!("{}: MISMATCH expected {}, got {}", label, expected, actual);
+            return false;
+        }
+    }
+    true
+}
+
+/// Checks every sample of a day, returning whether every answer matched
+fn check_day_samples(day: u32) -> Result<bool> {
+    let samples = find_samples(day)?;
+    if samples.is_empty() {
+        println!("Day {:2}: skipped, no samples with answers", day);
+    }
+    let mut all_correct = true;
+    for sample in samples.iter() {
+        let verifications = sample.verify(day)?;
+        if verifications.is_empty() {
+            let name = sample.name();
+            println!(
+                "Day {:2} {}: skipped, no answers in {}{}",
+                day, name, name, SAMPLE_ANSWERS_SUFFIX
+            );
+        }
+        for (part, verification) in verifications {
+            let label = format!("Day {:2} {} part {}", day, sample.name(), part);
+            all_correct &= report(&label, verification);
+        }
+    }
+    Ok(all_correct)
+}
+
+fn verify(days: &[u32]) -> Result<()> {
+    check_days(days, verify_day)
+}
+
+fn check_samples(days: &[u32]) -> Result<()> {
+    check_days(days, check_day_samples)
+}
+
+/// Runs a check for each day, failing if any day had a mismatch or an error
+fn check_days<F>(days: &[u32], check_day: F) -> Result<()>
+where
+    F: Fn(u32) -> Result<bool>,
+{
+    let mut failures = 0;
+    for &day in days {
+        match check_day(day) {
+            Ok(true) => {}
+            Ok(false) => failures += 1,
+            Err(e) => {
+                failures += 1;
+                println!("Day {:2}: error: {}", day, e);
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(Box::from(format!(
+            "{} of {} days failed",
+            failures,
+            days.len()
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+struct ProgramOptions {
+    day: u32,
+    input: String,
+}
+
+fn parse_program_args<I>(args: I) -> Result<ProgramOptions>
+where
+    I: Iterator<Item = String>,
+{
+    let (day, input) = parse_day_args(args, |arg, _| Err(unexpected_argument(arg)))?;
+    Ok(ProgramOptions {
+        day: elfcode_day(day)?,
+        input,
+    })
+}
+
+fn debug(options: &ProgramOptions) -> Result<()> {
+    if options.input == "-" {
+        return Err(Box::from("The debugger reads its commands from stdin"));
+    }
+    let stdin = io::stdin();
+    match load_program(options.day, &load_input(options.day, &options.input)?)? {
+        ElfCode::Device(program) => Debugger::new(program).repl(stdin.lock(), io::stdout())?,
+        ElfCode::Program(program) => Debugger::new(program).repl(stdin.lock(), io::stdout())?,
+    }
+    Ok(())
+}
+
+fn decompile(options: &ProgramOptions) -> Result<()> {
+    match load_program(options.day, &load_input(options.day, &options.input)?)? {
+        ElfCode::Device(program) => print!("{}", Decompiled::new(&program)),
+        ElfCode::Program(program) => print!("{}", Decompiled::new(&program)),
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+struct ProfileOptions {
+    day: u32,
+    input: String,
+    steps: u64,
+    registers: Vec<(usize, u64)>,
+    trace: Option<String>,
+}
+
+fn parse_profile_args<I>(args: I) -> Result<ProfileOptions>
+where
+    I: Iterator<Item = String>,
+{
+    let mut steps = PROFILE_STEPS;
+    let mut registers = vec![];
+    let mut trace = None;
+    let (day, input) = parse_day_args(args, |arg, args| {
+        match arg {
+            "--steps" => {
+                let value = option_value(arg, args)?;
+                steps = value
+                    .parse()
+                    .map_err(|_| format!("Invalid steps: {}", value))?;
+            }
+            "--set" => {
+                let value = option_value(arg, args)?;
+                let register = value
+                    .split_once('=')
+                    .and_then(|(r, v)| Some((r.parse().ok()?, v.parse().ok()?)))
+                    .ok_or_else(|| format!("Invalid register value: {}", value))?;
+                registers.push(register);
+            }
+            "--trace" => trace = Some(option_value(arg, args)?),
+            _ => return Err(unexpected_argument(arg)),
+        }
+        Ok(())
+    })?;
+    Ok(ProfileOptions {
+        day: elfcode_day(day)?,
+        input,
+        steps,
+        registers,
+        trace,
+    })
+}
+
+fn profile(options: &ProfileOptions) -> Result<()> {
+    match load_program(options.day, &load_input(options.day, &options.input)?)? {
+        ElfCode::Device(program) => profile_program(program, options),
+        ElfCode::Program(program) => profile_program(program, options),
+    }
+}
+
+fn profile_program<R: Word, const N: usize>(
+    mut program: Program<R, N>,
+    options: &ProfileOptions,
+) -> Result<()> {
+    for &(register, value) in options.registers.iter() {
+        *program
+            .registers
+            .get_mut(register)
+            .ok_or_else(|| format!("No register {}", register))? = value
+            .to_string()
+            .parse()
+            .map_err(|_| format!("{} does not fit in a register", value))?;
+    }
+    let mut tracer = match options.trace {
+        Some(_) => Tracer::with_history(TRACE_LENGTH),
+        None => Tracer::new(),
+    };
+    if !program.run_traced(&mut tracer, options.steps) {
+        println!(
+            "Stopped after {} instructions without halting",
+            options.steps
+        );
+    }
+    tracer.write_report(&program, HOT_SPOTS, &mut io::stdout())?;
+    if let Some(path) = &options.trace {
+        let file = File::create(path).map_err(|e| format!("Could not create {}: {}", path, e))?;
+        tracer.write_csv(&mut BufWriter::new(file))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+struct AnimateOptions {
+    day: u32,
+    input: String,
+    delay: Duration,
+}
+
+fn parse_animate_args<I>(args: I) -> Result<AnimateOptions>
+where
+    I: Iterator<Item = String>,
+{
+    let mut delay = Duration::from_millis(ANIMATION_DELAY_MS);
+    let (day, input) = parse_day_args(args, |arg, args| match arg {
+        "--delay" => {
+            let value = option_value(arg, args)?;
+            let ms = value
+                .parse()
+                .map_err(|_| format!("Invalid delay: {}", value))?;
+            delay = Duration::from_millis(ms);
+            Ok(())
+        }
+        _ => Err(unexpected_argument(arg)),
+    })?;
+    Ok(AnimateOptions { day, input, delay })
+}
+
+fn run_animation(options: &AnimateOptions) -> Result<()> {
+    if options.input == "-" {
+        return Err(Box::from("The animation reads its controls from stdin"));
+    }
+    let input = load_input(options.day, &options.input)?;
+    animate(options.day, &input, options.delay)
+}
+
+#[derive(Debug, PartialEq)]
+struct ImageOptions {
+    day: u32,
+    input: String,
+    output: Option<String>,
+    /// The format of the frames, `None` for a single image of the final state
+    frame_format: Option<ImageFormat>,
+    frames: FrameOptions,
+}
+
+fn parse_image_args<I>(args: I) -> Result<ImageOptions>
+where
+    I: Iterator<Item = String>,
+{
+    let mut output = None;
+    let mut write_frames = false;
+    let mut format = ImageFormat::Png;
+    let mut frames = FrameOptions {
+        scale: 1,
+        every: 1,
+        limit: FRAME_LIMIT,
+    };
+    let (day, input) = parse_day_args(args, |arg, args| {
+        match arg {
+            "--output" => output = Some(option_value(arg, args)?),
+            "--frames" => write_frames = true,
+            "--image-format" => {
+                let value = option_value(arg, args)?;
+                format = ImageFormat::from_name(&value)
+                    .ok_or_else(|| format!("Invalid image format: {}", value))?;
+            }
+            "--scale" | "--every" | "--limit" => {
+                let value = option_value(arg, args)?;
+                let number = match value.parse() {
+                    Ok(number) if number > 0 => number,
+                    _ => return Err(Box::from(format!("Invalid {}: {}", &arg[2..], value))),
+                };
+                match arg {
+                    "--scale" => frames.scale = number,
+                    "--every" => frames.every = number,
+                    _ => frames.limit = number,
+                }
+            }
+            _ => return Err(unexpected_argument(arg)),
+        }
+        Ok(())
+    })?;
+    Ok(ImageOptions {
+        day,
+        input,
+        output,
+        frame_format: if write_frames { Some(format) } else { None },
+        frames,
+    })
+}
+
+fn export_image(options: &ImageOptions) -> Result<()> {
+    let input = load_input(options.day, &options.input)?;
+    match options.frame_format {
+        None => {
+            let default_output = format!("day-{}.png", options.day);
+            let output = options.output.as_ref().unwrap_or(&default_output);
+            let image = draw(options.day, &input)?.scaled(options.frames.scale);
+            image
+                .save(Path::new(output))
+                .map_err(|e| format!("Could not write {}: {}", output, e))?;
+            println!(
+                "Wrote a {}x{} image to {}",
+                image.width(),
+                image.height(),
+                output
+            );
+        }
+        Some(format) => {
+            let default_output = format!("day-{}-frames", options.day);
+            let output = options.output.as_ref().unwrap_or(&default_output);
+            let mut frames = FrameWriter::new(Path::new(output), format)
+                .map_err(|e| format!("Could not create {}: {}", output, e))?;
+            write_frames(options.day, &input, &mut frames, options.frames)?;
+            println!("Wrote {} frames to {}", frames.frames(), output);
+        }
+    }
+    Ok(())
+}
+
+fn parse_new_args<I>(mut args: I) -> Result<u32>
+where
+    I: Iterator<Item = String>,
+{
+    let arg = args.next().ok_or("Missing day")?;
+    let day = match arg.parse() {
+        Ok(day) if (1..=DAYS).contains(&day) => day,
+        _ => return Err(Box::from(format!("Invalid day: {}", arg))),
+    };
+    if let Some(arg) = args.next() {
+        return Err(Box::from(format!("Unexpected argument: {}", arg)));
+    }
+    Ok(day)
+}
+
+fn new_day(day: u32) -> Result<()> {
+    let root = root_dir().canonicalize()?;
+    for path in scaffold(&root, day)? {
+        println!(
+            "Wrote {}",
+            path.strip_prefix(&root).unwrap_or(&path).display()
+        );
+    }
+    println!("Rebuild aoc to run day {} with it", day);
+    Ok(())
+}
+
+/// Takes the `--log <filter>` that may come before the command
+fn parse_log_arg<I>(args: &mut Peekable<I>) -> Result<Option<String>>
+where
+    I: Iterator<Item = String>,
+{
+    if args.peek().map(String::as_str) != Some("--log") {
+        return Ok(None);
+    }
+    args.next();
+    Ok(Some(args.next().ok_or("Missing value for --log")?))
+}
+
+fn main() {
+    let mut args = env::args().skip(1).peekable();
+    match parse_log_arg(&mut args) {
+        Ok(filter) => common::init_logging(filter.as_deref()),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+    let result = match args.next().as_deref() {
+        Some("run") => parse_run_args(args).and_then(|options| run(&options)),
+        Some("verify") => parse_verify_args(args).and_then(|days| verify(&days)),
+        Some("samples") => parse_verify_args(args).and_then(|days| check_samples(&days)),
+        Some("debug") => parse_program_args(args).and_then(|options| debug(&options)),
+        Some("decompile") => parse_program_args(args).and_then(|options| decompile(&options)),
+        Some("profile") => parse_profile_args(args).and_then(|options| profile(&options)),
+        Some("animate") => parse_animate_args(args).and_then(|options| run_animation(&options)),
+        Some("image") => parse_image_args(args).and_then(|options| export_image(&options)),
+        Some("new") => parse_new_args(args).and_then(new_day),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn to_args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|s| String::from(*s))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn it_parses_the_day_and_input_before_other_options() {
+        let mut options = vec![];
+        let (day, input) = parse_day_args(
+            to_args(&["19", "--steps", "5", "--input", "path"]),
+            |arg, args| {
+                options.push((String::from(arg), option_value(arg, args)?));
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!((day, input.as_str()), (19, "path"));
+        assert_eq!(options, vec![(String::from("--steps"), String::from("5"))]);
+        let reject = |arg: &str, _: &mut _| Err(unexpected_argument(arg));
+        assert_eq!(
+            parse_day_args(to_args(&["x"]), reject)
+                .unwrap_err()
+                .to_string(),
+            "Day must be a number"
+        );
+        assert_eq!(
+            parse_day_args(to_args(&["19", "--input"]), reject)
+                .unwrap_err()
+                .to_string(),
+            "Missing value for --input"
+        );
+        assert_eq!(
+            parse_day_args(to_args(&["19", "--part"]), reject)
+                .unwrap_err()
+                .to_string(),
+            "Unexpected argument: --part"
+        );
+    }
+
+    #[test]
+    fn it_parses_run_args_correctly() {
+        let options = parse_run_args(to_args(&[
+            "15", "--part", "2", "--input", "path", "--format", "json",
+        ]))
+        .unwrap();
+        assert_eq!(
+            options,
+            RunOptions {
+                day: 15,
+                parts: vec![2],
+                input: String::from("path"),
+                format: Format::Json,
+            }
+        );
+        let options = parse_run_args(to_args(&["3"])).unwrap();
+        assert_eq!(options.parts, vec![1, 2]);
+        assert_eq!(options.input, default_input_path(3).display().to_string());
+        assert_eq!(options.format, Format::Text);
+    }
+
+    #[test]
+    fn it_rejects_invalid_run_args() {
+        assert!(parse_run_args(to_args(&[])).is_err());
+        assert!(parse_run_args(to_args(&["x"])).is_err());
+        assert!(parse_run_args(to_args(&["1", "--part", "3"])).is_err());
+        assert!(parse_run_args(to_args(&["1", "--input"])).is_err());
+        assert!(parse_run_args(to_args(&["1", "--verbose"])).is_err());
+        assert!(parse_run_args(to_args(&["1", "--format", "csv"])).is_err());
+    }
+
+    #[test]
+    fn it_converts_results_to_json() {
+        let result = PartResult {
+            part: 2,
+            answer: String::from("82,104"),
+            parse_time: Duration::from_micros(15),
+            solve_time: Duration::from_millis(3),
+        };
+        assert_eq!(
+            to_json(13, &result),
+            json!({
+                "day": 13,
+                "part": 2,
+                "answer": "82,104",
+                "parse_time_ns": 15_000,
+                "solve_time_ns": 3_000_000,
+            })
+        );
+    }
+
+    #[test]
+    fn it_parses_verify_args_correctly() {
+        assert_eq!(
+            parse_verify_args(to_args(&[])).unwrap().len(),
+            DAYS as usize
+        );
+        assert_eq!(
+            parse_verify_args(to_args(&["3", "17"])).unwrap(),
+            vec![3, 17]
+        );
+        assert!(parse_verify_args(to_args(&["26"])).is_err());
+        assert!(parse_verify_args(to_args(&["x"])).is_err());
+    }
+
+    #[test]
+    fn it_parses_program_args_correctly() {
+        assert_eq!(
+            parse_program_args(to_args(&["21", "--input", "path"])).unwrap(),
+            ProgramOptions {
+                day: 21,
+                input: String::from("path"),
+            }
+        );
+        assert_eq!(
+            parse_program_args(to_args(&["19"])).unwrap().input,
+            default_input_path(19).display().to_string()
+        );
+        assert!(parse_program_args(to_args(&[])).is_err());
+        assert_eq!(
+            parse_program_args(to_args(&["18"]))
+                .unwrap_err()
+                .to_string(),
+            "Day 18 is not an ElfCode program, only days 16, 19 and 21 are"
+        );
+        assert!(parse_profile_args(to_args(&["1", "--steps", "100"])).is_err());
+        assert!(parse_program_args(to_args(&["19", "--part", "1"])).is_err());
+    }
+
+    #[test]
+    fn it_parses_profile_args_correctly() {
+        let options = parse_profile_args(to_args(&[
+            "19",
+            "--steps",
+            "100",
+            "--set",
+            "0=1",
+            "--trace",
+            "trace.csv",
+        ]))
+        .unwrap();
+        assert_eq!(
+            options,
+            ProfileOptions {
+                day: 19,
+                input: default_input_path(19).display().to_string(),
+                steps: 100,
+                registers: vec![(0, 1)],
+                trace: Some(String::from("trace.csv")),
+            }
+        );
+        assert_eq!(
+            parse_profile_args(to_args(&["21"])).unwrap().steps,
+            PROFILE_STEPS
+        );
+        assert!(parse_profile_args(to_args(&["19", "--set", "0"])).is_err());
+        assert!(parse_profile_args(to_args(&["19", "--steps", "x"])).is_err());
+    }
+
+    #[test]
+    fn it_parses_animate_args_correctly() {
+        assert_eq!(
+            parse_animate_args(to_args(&["13", "--input", "path", "--delay", "20"])).unwrap(),
+            AnimateOptions {
+                day: 13,
+                input: String::from("path"),
+                delay: Duration::from_millis(20),
+            }
+        );
+        assert_eq!(
+            parse_animate_args(to_args(&["18"])).unwrap().delay,
+            Duration::from_millis(ANIMATION_DELAY_MS)
+        );
+        assert!(parse_animate_args(to_args(&["18", "--delay", "fast"])).is_err());
+        assert!(parse_animate_args(to_args(&["18", "--part", "1"])).is_err());
+    }
+
+    #[test]
+    fn it_parses_image_args_correctly() {
+        let options = parse_image_args(to_args(&[
+            "17",
+            "--frames",
+            "--image-format",
+            "ppm",
+            "--every",
+            "10",
+            "--scale",
+            "3",
+        ]))
+        .unwrap();
+        assert_eq!(
+            options,
+            ImageOptions {
+                day: 17,
+                input: default_input_path(17).display().to_string(),
+                output: None,
+                frame_format: Some(ImageFormat::Ppm),
+                frames: FrameOptions {
+                    scale: 3,
+                    every: 10,
+                    limit: FRAME_LIMIT,
+                },
+            }
+        );
+        let options = parse_image_args(to_args(&["6", "--output", "areas.ppm"])).unwrap();
+        assert_eq!(options.output, Some(String::from("areas.ppm")));
+        assert_eq!(options.frame_format, None);
+        assert!(parse_image_args(to_args(&["6", "--scale", "0"])).is_err());
+        assert!(parse_image_args(to_args(&["6", "--limit"])).is_err());
+        assert!(parse_image_args(to_args(&["6", "--image-format", "gif"])).is_err());
+        assert!(parse_image_args(to_args(&["17", "--format", "ppm"])).is_err());
+    }
+
+    #[test]
+    fn it_parses_new_args_correctly() {
+        assert_eq!(parse_new_args(to_args(&["25"])).unwrap(), 25);
+        assert!(parse_new_args(to_args(&[])).is_err());
+        assert!(parse_new_args(to_args(&["26"])).is_err());
+        assert!(parse_new_args(to_args(&["3", "--input", "path"])).is_err());
+    }
+
+    #[test]
+    fn it_parses_the_log_filter_before_the_command() {
+        let mut args = to_args(&["--log", "day_24=trace", "run", "24"]).peekable();
+        assert_eq!(
+            parse_log_arg(&mut args).unwrap(),
+            Some(String::from("day_24=trace"))
+        );
+        assert_eq!(args.next(), Some(String::from("run")));
+        let mut args = to_args(&["run", "24"]).peekable();
+        assert_eq!(parse_log_arg(&mut args).unwrap(), None);
+        assert_eq!(args.next(), Some(String::from("run")));
+        assert!(parse_log_arg(&mut to_args(&["--log"]).peekable()).is_err());
+    }
+}
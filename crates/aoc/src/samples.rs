@@ -0,0 +1,113 @@
+use crate::{day_dir, load_input, solve, Answers, Result, Verification};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Suffix of the file next to a sample holding its answers
+pub const SAMPLE_ANSWERS_SUFFIX: &str = ".answers.toml";
+
+/// An example input from a puzzle description with the answers it should give
+///
+/// A sample is any file with `sample` in its name in a day's directory that has an answers file
+/// named after it, like `sample-input` and `sample-input.answers.toml`. The answers file has the
+/// same format as `answers.toml` and only the parts it lists are checked.
+#[derive(Debug, PartialEq)]
+pub struct Sample {
+    pub path: PathBuf,
+    pub answers: Answers,
+}
+
+impl Sample {
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Solves each part the sample has an answer for and compares it with the answer
+    pub fn verify(&self, day: u32) -> Result<Vec<(u8, Verification)>> {
+        let input = load_input(day, &self.path.display().to_string())?;
+        let mut verifications = vec![];
+        for part in 1..=2 {
+            if self.answers.get(part).is_some() {
+                let actual = solve(day, part, &input)?.answer;
+                verifications.push((part, self.answers.verify(part, actual)));
+            }
+        }
+        Ok(verifications)
+    }
+}
+
+/// Path of the answers file for the given sample file
+pub fn sample_answers_path(sample_path: &Path) -> PathBuf {
+    let mut path = sample_path.as_os_str().to_owned();
+    path.push(SAMPLE_ANSWERS_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Finds the samples in a day's directory, sorted by name
+pub fn find_samples(day: u32) -> Result<Vec<Sample>> {
+    find_samples_in(&day_dir(day))
+}
+
+fn find_samples_in(dir: &Path) -> Result<Vec<Sample>> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Could not read {}: {}", dir.display(), e))?;
+    let mut paths = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if name.contains("sample") && !name.ends_with(SAMPLE_ANSWERS_SUFFIX) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    paths
+        .into_iter()
+        .filter(|path| sample_answers_path(path).is_file())
+        .map(|path| {
+            let answers = Answers::load(&sample_answers_path(&path))?;
+            Ok(Sample { path, answers })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::DAYS;
+
+    #[test]
+    fn it_pairs_samples_with_their_answers() {
+        assert_eq!(
+            sample_answers_path(Path::new("solutions/day-25/sample-a")),
+            PathBuf::from("solutions/day-25/sample-a.answers.toml")
+        );
+        let samples = find_samples(25).unwrap();
+        let names: Vec<_> = samples.iter().map(|s| s.name()).collect();
+        assert_eq!(names, vec!["sample-a", "sample-b", "sample-c", "sample-d"]);
+        assert_eq!(samples[0].answers.get(1), Some("2"));
+        // Day 10's expected rendering has no answers file of its own
+        let samples = find_samples(10).unwrap();
+        let names: Vec<_> = samples.iter().map(|s| s.name()).collect();
+        assert_eq!(names, vec!["sample-input"]);
+    }
+
+    #[test]
+    fn it_solves_every_sample() {
+        for day in 1..=DAYS {
+            for sample in find_samples(day).unwrap() {
+                for (part, verification) in sample.verify(day).unwrap() {
+                    assert_eq!(
+                        verification,
+                        Verification::Correct,
+                        "day {} {} part {}",
+                        day,
+                        sample.name(),
+                        part
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use crate::{Result, DAYS};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const YEAR: u32 = 2018;
+
+const MANIFEST_TEMPLATE: &str = r#"[package]
+name = "advent-of-code-day-{day}"
+version = "0.1.0"
+authors = ["Thomas Lent <tcl293@gmail.com>"]
+edition = "2018"
+
+[lib]
+name = "day_{day}"
+path = "src/lib.rs"
+
+[features]
+embedded-input = []
+
+[dependencies]
+common = { path = "../../crates/common" }
+
+[dev-dependencies]
+bench = { path = "../../crates/bench" }
+criterion = "0.2"
+
+[[bench]]
+name = "day_{day}_bench"
+harness = false
+"#;
+
+const LIB_TEMPLATE: &str = r#"use common::{end_of_input, lines, ParseResult, Solution};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        solve_part_one(input)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        solve_part_two(input)
+    }
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<String>> {
+    let values: Vec<_> = lines(input)
+        .filter(|line| !line.text.trim().is_empty())
+        .map(|line| String::from(line.text))
+        .collect();
+    if values.is_empty() {
+        return Err(end_of_input(input, "a line"));
+    }
+    Ok(values)
+}
+
+pub fn solve_part_one(lines: &[String]) -> usize {
+    lines.len()
+}
+
+pub fn solve_part_two(lines: &[String]) -> usize {
+    lines.len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_rejects_blank_input() {
+        assert_eq!(
+            parse_input("\n").unwrap_err().to_string(),
+            "line 2, column 1: expected a line, found nothing"
+        );
+    }
+}
+"#;
+
+/// Answers to fill in, commented out so that nothing is checked until they are
+const ANSWERS_TEMPLATE: &str = "# part_one = \"\"\n# part_two = \"\"\n";
+
+const MAIN_TEMPLATE: &str = r#"use day_{day}::Day{day};
+
+#[cfg(feature = "embedded-input")]
+const EMBEDDED_INPUT: Option<&str> = Some(include_str!("../input"));
+#[cfg(not(feature = "embedded-input"))]
+const EMBEDDED_INPUT: Option<&str> = None;
+
+fn main() {
+    common::run::<Day{day}>(EMBEDDED_INPUT);
+}
+"#;
+
+const BENCH_TEMPLATE: &str = r#"use bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_{day}::Day{day};
+
+const INPUT: &str = include_str!("../input");
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_solution::<Day{day}>(c, {day}, INPUT);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
+"#;
+
+/// Creates the crate for a day in `root` and registers it with the workspace, the runner and the
+/// README, returning the paths written
+///
+/// The crate solves both parts with placeholders and comes with an empty `input` and
+/// `sample-input`, each with an answers file of commented out answers to fill in. Nothing is written if the day
+/// already has a directory or one of the files to register it in can't be changed.
+pub fn scaffold(root: &Path, day: u32) -> Result<Vec<PathBuf>> {
+    if !(1..=DAYS).contains(&day) {
+        return Err(Box::from(format!("Invalid day: {}", day)));
+    }
+    let day_dir = root.join("solutions").join(day_dir_name(day));
+    if day_dir.exists() {
+        return Err(Box::from(format!("{} already exists", day_dir.display())));
+    }
+    let mut edits = vec![];
+    for (path, register) in registrations(root).iter() {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let edited = register(&text, day).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if let Some(edited) = edited {
+            edits.push((path.clone(), edited));
+        }
+    }
+    let fill = |template: &str| template.replace("{day}", &day.to_string());
+    let files = [
+        ("Cargo.toml", fill(MANIFEST_TEMPLATE)),
+        ("src/lib.rs", fill(LIB_TEMPLATE)),
+        ("src/main.rs", fill(MAIN_TEMPLATE)),
+        (
+            &format!("benches/day_{}_bench.rs", day),
+            fill(BENCH_TEMPLATE),
+        ),
+        ("input", String::new()),
+        ("answers.toml", String::from(ANSWERS_TEMPLATE)),
+        ("sample-input", String::new()),
+        ("sample-input.answers.toml", String::from(ANSWERS_TEMPLATE)),
+    ];
+    let mut written = vec![];
+    for (name, contents) in files.iter() {
+        let path = day_dir.join(name);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, contents)?;
+        written.push(path);
+    }
+    for (path, edited) in edits {
+        fs::write(&path, edited)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Returns the file with a day's line added, or `None` if the day already has one
+type Register = fn(&str, u32) -> Result<Option<String>>;
+
+/// The files outside of the day's crate that list every day
+fn registrations(root: &Path) -> [(PathBuf, Register); 4] {
+    [
+        (root.join("Cargo.toml"), register_member),
+        (root.join("crates/aoc/Cargo.toml"), register_dependency),
+        (root.join("crates/aoc/src/lib.rs"), register_solver),
+        (root.join("README.md"), register_readme_row),
+    ]
+}
+
+fn day_dir_name(day: u32) -> String {
+    format!("day-{:02}", day)
+}
+
+/// Adds the day's crate to the workspace members unless a member already covers it
+fn register_member(manifest: &str, day: u32) -> Result<Option<String>> {
+    let member = format!("\"solutions/{}\"", day_dir_name(day));
+    let (start, line) = manifest
+        .lines()
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len() + 1;
+            Some((start, line))
+        })
+        .find(|(_, line)| line.trim_start().starts_with("members"))
+        .ok_or("No workspace members")?;
+    if line.contains("\"solutions/day-*\"") || line.contains(&member) {
+        return Ok(None);
+    }
+    let end = line
+        .rfind(']')
+        .ok_or("The workspace members must be on one line")?;
+    let separator = if line[..end].trim_end().ends_with('[') {
+        ""
+    } else {
+        ", "
+    };
+    let mut edited = String::from(manifest);
+    edited.insert_str(start + end, &format!("{}{}", separator, member));
+    Ok(Some(edited))
+}
+
+/// Adds the runner's dependency on the day's crate
+fn register_dependency(manifest: &str, day: u32) -> Result<Option<String>> {
+    let dependency = format!(
+        "advent-of-code-day-{} = {{ path = \"../../solutions/{}\" }}",
+        day,
+        day_dir_name(day)
+    );
+    insert_day_line(manifest, day, &dependency, |line| {
+        let name = line.strip_prefix("advent-of-code-day-")?;
+        name[..name.find(' ')?].parse().ok()
+    })
+}
+
+/// Adds the day to the runner's `find_solver`
+fn register_solver(source: &str, day: u32) -> Result<Option<String>> {
+    let arm = format!("        {} => solver::<day_{}::Day{}>(),", day, day, day);
+    insert_day_line(source, day, &arm, |line| {
+        let (day, _) = line.trim_start().split_once(" => solver::")?;
+        day.parse().ok()
+    })
+}
+
+/// Adds the day to the table of solutions, padded to the table's columns
+fn register_readme_row(readme: &str, day: u32) -> Result<Option<String>> {
+    let widths: Vec<_> = readme
+        .lines()
+        .find(|line| line.starts_with("| -"))
+        .ok_or("No table of solutions")?
+        .split('|')
+        .map(|cell| cell.trim().len())
+        .filter(|&width| width > 0)
+        .collect();
+    if widths.len() != 2 {
+        return Err(Box::from("The table of solutions must have two columns"));
+    }
+    let row = format!(
+        "| {:w0$} | {:w1$} |",
+        format!("[Day {}](https://adventofcode.com/{}/day/{})", day, YEAR, day),
+        format!(
+            "[main.rs](https://github.com/tlent/advent-of-code/blob/master/solutions/{}/src/main.rs)",
+            day_dir_name(day)
+        ),
+        w0 = widths[0],
+        w1 = widths[1]
+    );
+    insert_day_line(readme, day, &row, |line| {
+        let day = line.strip_prefix("| [Day ")?;
+        day[..day.find(']')?].parse().ok()
+    })
+}
+
+/// Inserts a line among the lines that `day_of` finds a day in, keeping them ordered by day, or
+/// returns `None` if the day already has a line
+fn insert_day_line<F>(text: &str, day: u32, new_line: &str, day_of: F) -> Result<Option<String>>
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    let lines: Vec<_> = text.lines().collect();
+    let days: Vec<_> = lines.iter().map(|line| day_of(line)).collect();
+    if days.contains(&Some(day)) {
+        return Ok(None);
+    }
+    let index = match days.iter().position(|&d| d.is_some_and(|d| d > day)) {
+        Some(index) => index,
+        None => {
+            days.iter()
+                .rposition(Option::is_some)
+                .ok_or("No days to add the day to")?
+                + 1
+        }
+    };
+    let mut edited: Vec<_> = lines[..index].to_vec();
+    edited.push(new_line);
+    edited.extend(&lines[index..]);
+    let mut edited = edited.join("\n");
+    if text.ends_with('\n') {
+        edited.push('\n');
+    }
+    Ok(Some(edited))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{root_dir, Answers};
+    use std::env;
+
+    const README: &str = "# Advent of Code
+
+| Problem                                        | Solution |
+| ---------------------------------------------- | -------- |
+| [Day 1](https://adventofcode.com/2018/day/1)   | x        |
+| [Day 3](https://adventofcode.com/2018/day/3)   | x        |
+";
+
+    #[test]
+    fn it_registers_days_in_order() {
+        let manifest = "[dependencies]\ncommon = { path = \"../common\" }\n\
+                        advent-of-code-day-9 = { path = \"../../solutions/day-09\" }\n\
+                        advent-of-code-day-10 = { path = \"../../solutions/day-10\" }\n";
+        let edited = register_dependency(manifest, 12).unwrap().unwrap();
+        assert!(edited.ends_with(
+            "day-10\" }\nadvent-of-code-day-12 = { path = \"../../solutions/day-12\" }\n"
+        ));
+        let edited = register_dependency(manifest, 2).unwrap().unwrap();
+        assert!(edited.contains("common = { path = \"../common\" }\nadvent-of-code-day-2 ="));
+        assert_eq!(register_dependency(manifest, 10).unwrap(), None);
+
+        let source = "    match day {\n        1 => solver::<day_1::Day1>(),\n        _ => None,\n";
+        assert_eq!(
+            register_solver(source, 2).unwrap().unwrap(),
+            "    match day {\n        1 => solver::<day_1::Day1>(),\n        \
+             2 => solver::<day_2::Day2>(),\n        _ => None,\n"
+        );
+        assert!(register_solver("fn main() {}", 2).is_err());
+    }
+
+    #[test]
+    fn it_registers_workspace_members() {
+        let manifest = "[workspace]\nmembers = [\"crates/*\"]\n";
+        assert_eq!(
+            register_member(manifest, 4).unwrap().unwrap(),
+            "[workspace]\nmembers = [\"crates/*\", \"solutions/day-04\"]\n"
+        );
+        assert_eq!(
+            register_member("members = []\n", 4).unwrap().unwrap(),
+            "members = [\"solutions/day-04\"]\n"
+        );
+        let manifest = "members = [\"crates/*\", \"solutions/day-*\"]\n";
+        assert_eq!(register_member(manifest, 4).unwrap(), None);
+    }
+
+    #[test]
+    fn it_pads_readme_rows_to_the_table() {
+        let edited = register_readme_row(README, 2).unwrap().unwrap();
+        assert!(edited.contains(
+            "| x        |\n\
+             | [Day 2](https://adventofcode.com/2018/day/2)   | \
+             [main.rs](https://github.com/tlent/advent-of-code/blob/master/solutions/day-02/src/main.rs) |\n\
+             | [Day 3]"
+        ));
+        assert_eq!(register_readme_row(README, 3).unwrap(), None);
+    }
+
+    #[test]
+    fn it_scaffolds_a_day() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("crates/aoc/src")).unwrap();
+        fs::write(root.join("Cargo.toml"), "members = [\"crates/*\"]\n").unwrap();
+        fs::write(
+            root.join("crates/aoc/Cargo.toml"),
+            "advent-of-code-day-1 = { path = \"../../solutions/day-01\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("crates/aoc/src/lib.rs"),
+            "1 => solver::<day_1::Day1>(),\n",
+        )
+        .unwrap();
+        fs::write(root.join("README.md"), README).unwrap();
+
+        let written = scaffold(&root, 2).unwrap();
+        let day_dir = root.join("solutions/day-02");
+        assert_eq!(written.len(), 12);
+        assert!(written.contains(&day_dir.join("benches/day_2_bench.rs")));
+        let lib = fs::read_to_string(day_dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day2;\n\nimpl Solution for Day2 {"));
+        assert_eq!(
+            fs::read_to_string(day_dir.join("sample-input")).unwrap(),
+            ""
+        );
+        let answers = Answers::load(&day_dir.join("sample-input.answers.toml")).unwrap();
+        assert_eq!(answers, Answers::default());
+        assert!(fs::read_to_string(root.join("crates/aoc/src/lib.rs"))
+            .unwrap()
+            .ends_with("2 => solver::<day_2::Day2>(),\n"));
+        assert!(scaffold(&root, 2).is_err());
+        assert!(scaffold(&root, 26).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn it_finds_every_day_registered() {
+        for (path, register) in registrations(&root_dir()).iter() {
+            let text = fs::read_to_string(path).unwrap();
+            for day in 1..=DAYS {
+                assert_eq!(
+                    register(&text, day).unwrap(),
+                    None,
+                    "day {} in {}",
+                    day,
+                    path.display()
+                );
+            }
+        }
+    }
+}
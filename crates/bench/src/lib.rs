@@ -0,0 +1,24 @@
+use common::Solution;
+use criterion::Criterion;
+use std::rc::Rc;
+
+/// Benchmarks parsing the input and solving each part of a day's solution
+///
+/// Each part is benchmarked against the same parsed input so parsing is only measured once.
+pub fn bench_solution<S>(c: &mut Criterion, day: u32, input: &'static str)
+where
+    S: Solution + 'static,
+    S::Input: 'static,
+{
+    c.bench_function(&format!("day {} parse", day), move |b| {
+        b.iter(|| S::parse(input).unwrap())
+    });
+    let parsed_input = Rc::new(S::parse(input).unwrap());
+    let part_one_input = Rc::clone(&parsed_input);
+    c.bench_function(&format!("day {} part one", day), move |b| {
+        b.iter(|| S::part_one(&part_one_input))
+    });
+    c.bench_function(&format!("day {} part two", day), move |b| {
+        b.iter(|| S::part_two(&parsed_input))
+    });
+}
@@ -0,0 +1,254 @@
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
+
+mod parse;
+mod snapshot;
+
+pub use crate::parse::{end_of_input, lines, section_lines, Cursor, Line, ParseError, ParseResult};
+pub use crate::snapshot::{assert_snapshot, diff, snapshot_path, UPDATE_SNAPSHOTS_VAR};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+/// Environment variable with the log filter used when none is given
+pub const LOG_FILTER_VAR: &str = "RUST_LOG";
+
+/// Sends log messages to stderr, filtered by `filter` or else by the `RUST_LOG` variable
+///
+/// A filter is a level like `debug`, a module like `day_24` for all of its messages, or a list
+/// of them like `day_15=debug,day_24=trace`. Without one nothing is logged.
+pub fn init_logging(filter: Option<&str>) {
+    let mut builder = env_logger::Builder::new();
+    match filter {
+        Some(filter) => builder.parse_filters(filter),
+        None => builder.parse_env(LOG_FILTER_VAR),
+    };
+    builder.format_timestamp(None);
+    // Only the first logger is kept, so initializing again is harmless
+    let _ = builder.try_init();
+}
+
+/// A solution to both parts of a day's puzzle
+///
+/// The puzzle input is parsed once and the parsed value is shared by both parts.
+pub trait Solution {
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    /// Whether the puzzle input is a short value that can be given directly instead of a file
+    const LITERAL_INPUT: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part_one(input: &Self::Input) -> Self::PartOne;
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo;
+}
+
+/// The answer to one part of a puzzle along with how long it took to find
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// Parses the input and solves the given part, timing the parse and the solve separately
+pub fn solve_timed<S: Solution>(input: &str, part: u8) -> Result<PartResult> {
+    if part != 1 && part != 2 {
+        return Err(Box::from(format!("Invalid part: {}", part)));
+    }
+    let start = Instant::now();
+    let parsed_input = S::parse(input)?;
+    let parse_time = start.elapsed();
+    let start = Instant::now();
+    let answer = match part {
+        1 => S::part_one(&parsed_input).to_string(),
+        _ => S::part_two(&parsed_input).to_string(),
+    };
+    let solve_time = start.elapsed();
+    Ok(PartResult {
+        part,
+        answer,
+        parse_time,
+        solve_time,
+    })
+}
+
+/// Parses the input and solves the given part, returning the displayed answer
+pub fn solve<S: Solution>(input: &str, part: u8) -> Result<String> {
+    solve_timed::<S>(input, part).map(|result| result.answer)
+}
+
+/// Loads the puzzle input given on the command line
+///
+/// `-` reads the input from stdin and anything else is the path to an input file. Days with a
+/// literal input also accept the value itself when no file exists at that path.
+pub fn load_input<S: Solution>(arg: &str) -> Result<String> {
+    if arg == "-" {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("Could not read input from stdin: {}", e))?;
+        return Ok(input);
+    }
+    let path = Path::new(arg);
+    if S::LITERAL_INPUT && !path.is_file() {
+        return Ok(String::from(arg));
+    }
+    fs::read_to_string(path).map_err(|e| Box::from(format!("Could not read input {}: {}", arg, e)))
+}
+
+fn parse_input_arg<I>(mut args: I) -> Result<Option<String>>
+where
+    I: Iterator<Item = String>,
+{
+    let mut input_arg = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => input_arg = Some(args.next().ok_or("Missing value for --input")?),
+            _ => return Err(Box::from(format!("Unexpected argument: {}", arg))),
+        }
+    }
+    Ok(input_arg)
+}
+
+fn run_with_args<S, I>(args: I, embedded_input: Option<&str>) -> Result<()>
+where
+    S: Solution,
+    I: Iterator<Item = String>,
+{
+    let input = match (parse_input_arg(args)?, embedded_input) {
+        (Some(arg), _) => load_input::<S>(&arg)?,
+        (None, Some(input)) => String::from(input),
+        (None, None) => {
+            return Err(Box::from(
+                "Missing --input, the checked in input is only embedded with the `embedded-input` feature",
+            ))
+        }
+    };
+    let parsed_input = S::parse(&input)?;
+    println!("{}", S::part_one(&parsed_input));
+    println!("{}", S::part_two(&parsed_input));
+    Ok(())
+}
+
+/// Entry point shared by every day's binary
+///
+/// Solves both parts for the input given with `--input`, falling back to the embedded input.
+/// Log messages are enabled with the `RUST_LOG` variable.
+pub fn run<S: Solution>(embedded_input: Option<&str>) {
+    init_logging(None);
+    if let Err(e) = run_with_args::<S, _>(env::args().skip(1), embedded_input) {
+        eprintln!("Error: {}", e);
+        eprintln!("USAGE: [--input <path|->]");
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+        type PartOne = i32;
+        type PartTwo = String;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            input
+                .split_whitespace()
+                .map(|s| s.parse().map_err(Box::from))
+                .collect()
+        }
+
+        fn part_one(input: &Self::Input) -> i32 {
+            input.iter().sum()
+        }
+
+        fn part_two(input: &Self::Input) -> String {
+            format!("{} numbers", input.len())
+        }
+    }
+
+    struct Literal;
+
+    impl Solution for Literal {
+        type Input = u32;
+        type PartOne = u32;
+        type PartTwo = u32;
+
+        const LITERAL_INPUT: bool = true;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.trim().parse()?)
+        }
+
+        fn part_one(input: &Self::Input) -> u32 {
+            *input
+        }
+
+        fn part_two(input: &Self::Input) -> u32 {
+            input * 2
+        }
+    }
+
+    fn to_args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|s| String::from(*s))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn it_solves_each_part() {
+        assert_eq!(solve::<Sum>("1 2 3", 1).unwrap(), "6");
+        assert_eq!(solve::<Sum>("1 2 3", 2).unwrap(), "3 numbers");
+    }
+
+    #[test]
+    fn it_returns_errors_for_invalid_input_and_part() {
+        assert!(solve::<Sum>("1 a", 1).is_err());
+        assert!(solve::<Sum>("1 2", 3).is_err());
+    }
+
+    #[test]
+    fn it_times_each_part() {
+        let result = solve_timed::<Sum>("1 2 3", 1).unwrap();
+        assert_eq!(result.part, 1);
+        assert_eq!(result.answer, "6");
+        assert!(solve_timed::<Sum>("1 2 3", 0).is_err());
+    }
+
+    #[test]
+    fn it_loads_literal_input() {
+        assert_eq!(load_input::<Literal>("5034").unwrap(), "5034");
+        assert!(load_input::<Sum>("5034").is_err());
+    }
+
+    #[test]
+    fn it_parses_the_input_arg() {
+        assert_eq!(parse_input_arg(to_args(&[])).unwrap(), None);
+        assert_eq!(
+            parse_input_arg(to_args(&["--input", "-"])).unwrap(),
+            Some(String::from("-"))
+        );
+        assert!(parse_input_arg(to_args(&["--input"])).is_err());
+        assert!(parse_input_arg(to_args(&["input"])).is_err());
+    }
+
+    #[test]
+    fn it_falls_back_to_the_embedded_input() {
+        assert!(run_with_args::<Literal, _>(to_args(&["--input", "7"]), None).is_ok());
+        assert!(run_with_args::<Sum, _>(to_args(&[]), Some("1 2")).is_ok());
+        assert!(run_with_args::<Sum, _>(to_args(&[]), None).is_err());
+    }
+}
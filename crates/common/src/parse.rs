@@ -0,0 +1,299 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+pub type ParseResult<T> = ::std::result::Result<T, ParseError>;
+
+/// A problem with the puzzle input, at a 1-based line and column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new<E: Into<String>, F: Into<String>>(
+        line: usize,
+        column: usize,
+        expected: E,
+        found: F,
+    ) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+}
+
+impl ParseError {
+    /// An error for the cell at a 0-based `(x, y)` position of a map with a character per cell
+    pub fn at_cell<E: Into<String>>((x, y): (usize, usize), expected: E, found: char) -> Self {
+        Self::new(y + 1, x + 1, expected, found.to_string())
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "nothing")
+        } else {
+            write!(f, "{:?}", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of the puzzle input with its 1-based line number, for building errors that point
+/// into it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error at the byte `offset` of the line, naming the token found there
+    pub fn error<E: Into<String>>(&self, offset: usize, expected: E) -> ParseError {
+        let offset = offset.min(self.text.len());
+        let column = self.text[..offset].chars().count() + 1;
+        ParseError::new(self.number, column, expected, token(&self.text[offset..]))
+    }
+
+    /// An error for a line that doesn't have the expected form at all
+    pub fn mismatch<E: Into<String>>(&self, expected: E) -> ParseError {
+        ParseError::new(self.number, 1, expected, self.text)
+    }
+
+    /// Byte offset of `part`, a slice of this line, or the end of the line for other strings
+    pub fn offset_of(&self, part: &str) -> usize {
+        let offset = (part.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        if offset <= self.text.len() {
+            offset
+        } else {
+            self.text.len()
+        }
+    }
+
+    /// Parses `part`, a slice of this line, as what was expected there
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> ParseResult<T> {
+        part.parse().map_err(|_| {
+            let offset = self.offset_of(part);
+            ParseError::new(
+                self.number,
+                self.text[..offset].chars().count() + 1,
+                expected,
+                part,
+            )
+        })
+    }
+
+    /// A cursor for reading the line from its start
+    pub fn cursor(&self) -> Cursor<'a> {
+        Cursor {
+            line: *self,
+            offset: 0,
+        }
+    }
+
+    /// The character at the 0-based character `index`
+    pub fn char_at(&self, index: usize, expected: &str) -> ParseResult<char> {
+        self.text
+            .chars()
+            .nth(index)
+            .ok_or_else(|| ParseError::new(self.number, index + 1, expected, ""))
+    }
+}
+
+/// Reads a line from left to right, reporting errors where reading stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor<'a> {
+    line: Line<'a>,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// The unread part of the line
+    pub fn rest(&self) -> &'a str {
+        &self.line.text[self.offset..]
+    }
+
+    /// An error at the current position
+    pub fn error<E: Into<String>>(&self, expected: E) -> ParseError {
+        self.line.error(self.offset, expected)
+    }
+
+    /// Skips `literal`, which must come next
+    pub fn literal(&mut self, literal: &str) -> ParseResult<()> {
+        if self.rest().starts_with(literal) {
+            self.offset += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(format!("{:?}", literal)))
+        }
+    }
+
+    /// Reads an optionally signed integer
+    pub fn number<T: FromStr>(&mut self, expected: &str) -> ParseResult<T> {
+        let rest = self.rest();
+        let sign = if rest.starts_with(['-', '+']) { 1 } else { 0 };
+        let end = rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(rest.len(), |end| end + sign);
+        if end == sign {
+            return Err(self.error(expected));
+        }
+        let number = self.line.parse(&rest[..end], expected)?;
+        self.offset += end;
+        Ok(number)
+    }
+
+    /// Reads the next character if it is one that was expected
+    pub fn char<P: Fn(char) -> bool>(&mut self, expected: &str, predicate: P) -> ParseResult<char> {
+        match self.rest().chars().next() {
+            Some(c) if predicate(c) => {
+                self.offset += c.len_utf8();
+                Ok(c)
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    /// Reads letters, digits and underscores, which may be none
+    pub fn word(&mut self) -> &'a str {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.offset += end;
+        &rest[..end]
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    /// Checks that only whitespace is left
+    pub fn end(&mut self) -> ParseResult<()> {
+        self.skip_whitespace();
+        if self.rest().is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("the end of the line"))
+        }
+    }
+}
+
+/// The lines of the input, numbered from 1
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+/// An error for input that ended before something that was expected
+pub fn end_of_input<E: Into<String>>(input: &str, expected: E) -> ParseError {
+    ParseError::new(input.lines().count() + 1, 1, expected, "")
+}
+
+/// The lines of a section of the input, `section` must be a slice of `input`
+///
+/// Numbers the lines by where they are in the whole input.
+pub fn section_lines<'a>(input: &str, section: &'a str) -> impl Iterator<Item = Line<'a>> {
+    let offset = (section.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    let first_line = input
+        .get(..offset)
+        .map_or(1, |before| before.matches('\n').count() + 1);
+    section.lines().enumerate().map(move |(i, text)| Line {
+        number: first_line + i,
+        text,
+    })
+}
+
+/// The word or single symbol at the start of `s`
+fn token(s: &str) -> &str {
+    let end = match s.chars().next() {
+        None => 0,
+        Some(c) if c.is_alphanumeric() || c == '-' => s
+            .find(|c: char| !(c.is_alphanumeric() || c == '-'))
+            .unwrap_or(s.len()),
+        Some(c) => c.len_utf8(),
+    };
+    &s[..end]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_points_errors_into_lines() {
+        let input = "#1 @ 1,3: 4x4\n#2 @ x,1: 4x4";
+        let line = lines(input).nth(1).unwrap();
+        assert_eq!(line.number, 2);
+        let error = line.parse::<u32>(&line.text[5..6], "a number").unwrap_err();
+        assert_eq!(error, ParseError::new(2, 6, "a number", "x"));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 6: expected a number, found \"x\""
+        );
+        assert_eq!(line.error(8, "a colon").found, ":");
+        assert_eq!(line.error(10, "a size").found, "4x4");
+        assert_eq!(
+            line.char_at(20, "a size").unwrap_err().to_string(),
+            "line 2, column 21: expected a size, found nothing"
+        );
+        assert_eq!(line.mismatch("a claim").found, "#2 @ x,1: 4x4");
+        assert_eq!(
+            end_of_input(input, "a claim"),
+            ParseError::new(3, 1, "a claim", "")
+        );
+    }
+
+    #[test]
+    fn it_reads_lines_with_a_cursor() {
+        let line = lines("pos=<-1,20> r=x").next().unwrap();
+        let mut cursor = line.cursor();
+        cursor.literal("pos=<").unwrap();
+        assert_eq!(cursor.number::<i32>("a coordinate"), Ok(-1));
+        cursor.literal(",").unwrap();
+        assert_eq!(cursor.number::<i32>("a coordinate"), Ok(20));
+        assert_eq!(
+            cursor.literal(","),
+            Err(ParseError::new(1, 11, "\",\"", ">"))
+        );
+        cursor.literal("> r=").unwrap();
+        assert_eq!(
+            cursor.number::<u32>("a radius"),
+            Err(ParseError::new(1, 15, "a radius", "x"))
+        );
+        assert_eq!(
+            cursor
+                .char("a digit", |c| c.is_ascii_digit())
+                .unwrap_err()
+                .found,
+            "x"
+        );
+        assert_eq!(cursor.char("a letter", char::is_alphabetic), Ok('x'));
+        assert_eq!(cursor.end(), Ok(()));
+    }
+
+    #[test]
+    fn it_numbers_section_lines_by_their_place_in_the_input() {
+        let input = "a\nb\n\nc\nd";
+        let section = input.split("\n\n").nth(1).unwrap();
+        let numbers: Vec<_> = section_lines(input, section).map(|l| l.number).collect();
+        assert_eq!(numbers, vec![4, 5]);
+    }
+}
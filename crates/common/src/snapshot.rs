@@ -0,0 +1,122 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that makes `assert_snapshot` record renderings instead of checking them
+pub const UPDATE_SNAPSHOTS_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// Path of the snapshot `name` for the crate in `manifest_dir`
+pub fn snapshot_path(manifest_dir: &str, name: &str) -> PathBuf {
+    Path::new(manifest_dir)
+        .join("snapshots")
+        .join(format!("{}.txt", name))
+}
+
+/// Checks a rendering against the snapshot `name` kept in the crate's `snapshots` directory
+///
+/// Panics with a line diff when they differ. With `UPDATE_SNAPSHOTS` set the rendering is written
+/// to the snapshot instead, which is how new snapshots are recorded.
+pub fn assert_snapshot(manifest_dir: &str, name: &str, actual: &str) {
+    let path = snapshot_path(manifest_dir, name);
+    let update = env::var_os(UPDATE_SNAPSHOTS_VAR).is_some();
+    if let Err(message) = check_snapshot(&path, actual, update) {
+        panic!("{}", message);
+    }
+}
+
+fn check_snapshot(path: &Path, actual: &str, update: bool) -> Result<(), String> {
+    if update {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+        }
+        return fs::write(path, actual)
+            .map_err(|e| format!("Could not write snapshot {}: {}", path.display(), e));
+    }
+    let expected = fs::read_to_string(path).map_err(|e| {
+        format!(
+            "Could not read snapshot {}: {}\nRun the test with {}=1 to record it",
+            path.display(),
+            e,
+            UPDATE_SNAPSHOTS_VAR
+        )
+    })?;
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(format!(
+            "Snapshot {} does not match, - is the snapshot and + is the rendering:\n{}\
+             Run the test with {}=1 to accept the rendering",
+            path.display(),
+            diff(&expected, actual),
+            UPDATE_SNAPSHOTS_VAR
+        ))
+    }
+}
+
+/// The lines that differ between `expected` and `actual`, each prefixed by its line number and
+/// `-` if only `expected` has it or `+` if only `actual` has it
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+    // common[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let mut output = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            output.push_str(&format!("{:>4} - {}\n", i + 1, expected[i]));
+            i += 1;
+        } else {
+            output.push_str(&format!("{:>4} + {}\n", j + 1, actual[j]));
+            j += 1;
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_diffs_lines() {
+        assert_eq!(diff("a\nb\nc\n", "a\nb\nc\n"), "");
+        assert_eq!(
+            diff("#..\n.#.\n..#\n", "#..\n.X.\n..#\nend\n"),
+            "   2 - .#.\n   2 + .X.\n   4 + end\n"
+        );
+        assert_eq!(diff("a\nb\n", ""), "   1 - a\n   2 - b\n");
+    }
+
+    #[test]
+    fn it_checks_snapshots() {
+        assert_eq!(
+            snapshot_path("solutions/day-18", "sample-input"),
+            PathBuf::from("solutions/day-18/snapshots/sample-input.txt")
+        );
+        let dir = env::temp_dir().join(format!("snapshot-test-{}", std::process::id()));
+        let path = dir.join("snapshots").join("example.txt");
+        assert!(check_snapshot(&path, "#.#\n", false)
+            .unwrap_err()
+            .contains("UPDATE_SNAPSHOTS=1 to record it"));
+        assert_eq!(check_snapshot(&path, "#.#\n", true), Ok(()));
+        assert_eq!(check_snapshot(&path, "#.#\n", false), Ok(()));
+        let error = check_snapshot(&path, "#..\n", false).unwrap_err();
+        assert!(error.contains("   1 - #.#\n   1 + #..\n"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,173 @@
+//! Cycle detection for simulations that eventually repeat, to skip ahead to huge step counts
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in a sequence of states, found by remembering every state seen
+///
+/// After `start` steps the states repeat every `length` steps, possibly only up to the
+/// normalization the cycle was found with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle<S> {
+    pub start: usize,
+    pub length: usize,
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// The earliest step with a state equivalent to the state after `step` steps
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+
+    /// A state equivalent to the state after `step` steps
+    pub fn state_at(&self, step: usize) -> &S {
+        &self.states[self.equivalent_step(step)]
+    }
+
+    /// Number of whole cycles between the equivalent step and `step`
+    pub fn cycles_before(&self, step: usize) -> usize {
+        if step < self.start {
+            0
+        } else {
+            (step - self.start) / self.length
+        }
+    }
+
+    /// Every state from the initial one up to and including the first repeated one
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// Steps from the initial state until a state repeats, the states must eventually repeat
+pub fn find_cycle<S, F>(initial: S, step: F) -> Cycle<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    find_cycle_by(initial, step, S::clone)
+}
+
+/// Steps from the initial state until two states have the same normalized form
+///
+/// Normalizing lets states that only differ in ways that don't affect the next states, like
+/// their position, be treated as the same. The states themselves are kept as they were.
+pub fn find_cycle_by<S, K, F, N>(initial: S, step: F, normalize: N) -> Cycle<S>
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    N: FnMut(&S) -> K,
+{
+    match find_cycle_within(initial, step, normalize, usize::MAX) {
+        Ok(cycle) => cycle,
+        Err(_) => panic!("States never repeated"),
+    }
+}
+
+/// Like `find_cycle_by` but gives up after `max_steps` steps, returning the last state instead
+pub fn find_cycle_within<S, K, F, N>(
+    initial: S,
+    step: F,
+    normalize: N,
+    max_steps: usize,
+) -> Result<Cycle<S>, S>
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    N: FnMut(&S) -> K,
+{
+    let (mut states, start) = search(initial, step, normalize, max_steps);
+    match start {
+        Some(start) => Ok(Cycle {
+            start,
+            length: states.len() - 1 - start,
+            states,
+        }),
+        None => Err(states.pop().unwrap()),
+    }
+}
+
+/// The state after `steps` steps, skipping ahead as soon as a state repeats
+pub fn state_after<S, F>(initial: S, step: F, steps: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let (mut states, start) = search(initial, step, S::clone, steps);
+    match start {
+        Some(start) => {
+            let length = states.len() - 1 - start;
+            states.swap_remove(start + (steps - start) % length)
+        }
+        None => states.pop().unwrap(),
+    }
+}
+
+/// Records states until one repeats or `max_steps` steps have been taken, returning the
+/// states and the step the repeated state was first seen at
+fn search<S, K, F, N>(
+    initial: S,
+    mut step: F,
+    mut normalize: N,
+    max_steps: usize,
+) -> (Vec<S>, Option<usize>)
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    N: FnMut(&S) -> K,
+{
+    let mut first_seen = HashMap::new();
+    first_seen.insert(normalize(&initial), 0);
+    let mut states = vec![initial];
+    for current_step in 1..=max_steps {
+        let next = step(states.last().unwrap());
+        let previous_step = first_seen.insert(normalize(&next), current_step);
+        states.push(next);
+        if previous_step.is_some() {
+            return (states, previous_step);
+        }
+    }
+    (states, None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_finds_cycles() {
+        // 2, 4, 16, 256 % 100 = 56, 36, 96, 16, ...
+        let cycle = find_cycle(2u64, |&n| n * n % 100);
+        assert_eq!((cycle.start, cycle.length), (2, 4));
+        assert_eq!(cycle.states(), &[2, 4, 16, 56, 36, 96, 16]);
+        assert_eq!(*cycle.state_at(1), 4);
+        assert_eq!(*cycle.state_at(6), 16);
+        assert_eq!(*cycle.state_at(1_000_000_003), 56);
+        assert_eq!(cycle.cycles_before(1_000_000_003), 250_000_000);
+    }
+
+    #[test]
+    fn it_finds_cycles_of_normalized_states() {
+        // A pattern that moves one step right every two steps
+        let step = |&(position, phase): &(i64, u8)| (position + i64::from(phase), 1 - phase);
+        let cycle = find_cycle_by((10, 0), step, |&(_, phase)| phase);
+        assert_eq!((cycle.start, cycle.length), (0, 2));
+        assert_eq!(cycle.states(), &[(10, 0), (10, 1), (11, 0)]);
+        assert_eq!(cycle.equivalent_step(7), 1);
+        assert_eq!(cycle.cycles_before(7), 3);
+        let no_cycle = find_cycle_within(0, |&n| n + 1, |&n| n, 10);
+        assert_eq!(no_cycle, Err(10));
+    }
+
+    #[test]
+    fn it_skips_ahead_to_the_state_after_many_steps() {
+        assert_eq!(state_after(2u64, |&n| n * n % 100, 2), 16);
+        assert_eq!(state_after(2u64, |&n| n * n % 100, 1_000_000_003), 56);
+        assert_eq!(state_after(0u64, |&n| n + 1, 50), 50);
+    }
+}
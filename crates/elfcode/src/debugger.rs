@@ -0,0 +1,404 @@
+use crate::{Program, Word};
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+const PROMPT: &str = "(elfcode) ";
+
+const HELP: &str = "step, s [count]       Execute up to count instructions, 1 by default
+continue, c           Run until a breakpoint, a watchpoint or the program halts
+break, b <line>       Stop before executing the instruction at line
+delete, d <line>      Remove the breakpoint at line
+watch, w <register>   Stop after the register changes
+unwatch <register>    Remove the watchpoint on the register
+set <register> <val>  Change the value of the register
+registers, r          Print the registers
+list, l [count]       List count instructions from ip, 5 by default
+info, i               List breakpoints and watchpoints
+help, h               Print this message
+quit, q               Exit the debugger
+
+An empty line repeats the previous command.";
+
+/// A command entered in the debugger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command<R> {
+    Step(usize),
+    Continue,
+    Break(usize),
+    Delete(usize),
+    Watch(usize),
+    Unwatch(usize),
+    Set(usize, R),
+    Registers,
+    List(usize),
+    Info,
+    Help,
+    Quit,
+}
+
+impl<R: Word> FromStr for Command<R> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split_whitespace().collect();
+        let number = |i: usize| -> Result<usize, String> {
+            let part = parts.get(i).ok_or("Missing argument")?;
+            part.parse()
+                .map_err(|_| format!("Expected a number: {}", part))
+        };
+        let optional_number = |i: usize, default: usize| {
+            if parts.len() > i {
+                number(i)
+            } else {
+                Ok(default)
+            }
+        };
+        let expected_parts = match parts.first().cloned() {
+            Some("set") => 3,
+            Some("step") | Some("s") | Some("list") | Some("l") => parts.len().min(2),
+            Some("break") | Some("b") | Some("delete") | Some("d") | Some("watch") | Some("w")
+            | Some("unwatch") => 2,
+            _ => 1,
+        };
+        if parts.len() > expected_parts {
+            return Err(format!("Too many arguments: {}", s.trim()));
+        }
+        let command = match parts.first().cloned() {
+            Some("step") | Some("s") => Command::Step(optional_number(1, 1)?),
+            Some("continue") | Some("c") => Command::Continue,
+            Some("break") | Some("b") => Command::Break(number(1)?),
+            Some("delete") | Some("d") => Command::Delete(number(1)?),
+            Some("watch") | Some("w") => Command::Watch(number(1)?),
+            Some("unwatch") => Command::Unwatch(number(1)?),
+            Some("set") => {
+                let value = parts
+                    .get(2)
+                    .and_then(|v| v.parse().ok())
+                    .ok_or_else(|| format!("Invalid value: {}", s.trim()))?;
+                Command::Set(number(1)?, value)
+            }
+            Some("registers") | Some("r") => Command::Registers,
+            Some("list") | Some("l") => Command::List(optional_number(1, 5)?),
+            Some("info") | Some("i") => Command::Info,
+            Some("help") | Some("h") => Command::Help,
+            Some("quit") | Some("q") => Command::Quit,
+            Some(name) => return Err(format!("Unknown command: {}, try help", name)),
+            None => return Err(String::from("Missing command")),
+        };
+        Ok(command)
+    }
+}
+
+/// Why the debugger stopped running the program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop<R> {
+    /// Every requested step was executed
+    Stepped,
+    /// The instruction pointer reached a breakpoint, the instruction there hasn't run yet
+    Breakpoint(usize),
+    /// A watched register changed
+    Watchpoint {
+        register: usize,
+        old: R,
+        new: R,
+    },
+    Halted,
+}
+
+/// Runs a program with breakpoints on instructions and watchpoints on registers
+#[derive(Debug, Clone)]
+pub struct Debugger<R, const N: usize> {
+    pub program: Program<R, N>,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+}
+
+impl<R: Word, const N: usize> Debugger<R, N> {
+    pub fn new(program: Program<R, N>) -> Self {
+        Self {
+            program,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, line: usize) {
+        self.breakpoints.insert(line);
+    }
+
+    /// Returns whether there was a breakpoint at the line
+    pub fn remove_breakpoint(&mut self, line: usize) -> bool {
+        self.breakpoints.remove(&line)
+    }
+
+    /// Panics if the register doesn't exist
+    pub fn add_watchpoint(&mut self, register: usize) {
+        assert!(register < N, "Register {} out of range", register);
+        self.watchpoints.insert(register);
+    }
+
+    /// Returns whether the register was watched
+    pub fn remove_watchpoint(&mut self, register: usize) -> bool {
+        self.watchpoints.remove(&register)
+    }
+
+    /// Executes up to `count` instructions, stopping early on breakpoints, watchpoints or when
+    /// halted
+    pub fn step(&mut self, count: usize) -> Stop<R> {
+        for remaining in (0..count).rev() {
+            if let Some(stop) = self.advance() {
+                return stop;
+            }
+            let ip = self.program.ip();
+            if remaining > 0 && self.breakpoints.contains(&ip) {
+                return Stop::Breakpoint(ip);
+            }
+        }
+        Stop::Stepped
+    }
+
+    /// Executes at least one instruction and keeps going until something stops the program
+    pub fn resume(&mut self) -> Stop<R> {
+        loop {
+            if let Some(stop) = self.advance() {
+                return stop;
+            }
+            let ip = self.program.ip();
+            if self.breakpoints.contains(&ip) {
+                return Stop::Breakpoint(ip);
+            }
+        }
+    }
+
+    /// Executes one instruction, returning why the program should stop if it should
+    fn advance(&mut self) -> Option<Stop<R>> {
+        let before = self.program.registers;
+        if !self.program.step() {
+            return Some(Stop::Halted);
+        }
+        let after = &self.program.registers;
+        self.watchpoints
+            .iter()
+            .find(|&&r| before[r] != after[r])
+            .map(|&register| Stop::Watchpoint {
+                register,
+                old: before[register],
+                new: after[register],
+            })
+    }
+
+    /// Runs a command, writing what happened to `output`, returns false when the debugger
+    /// should exit
+    pub fn execute<W: Write>(&mut self, command: &Command<R>, output: &mut W) -> io::Result<bool> {
+        match *command {
+            Command::Step(count) => {
+                let stop = self.step(count);
+                self.write_stop(stop, output)?;
+            }
+            Command::Continue => {
+                let stop = self.resume();
+                self.write_stop(stop, output)?;
+            }
+            Command::Break(line) => {
+                self.add_breakpoint(line);
+                writeln!(output, "Breakpoint at {}", line)?;
+            }
+            Command::Delete(line) => {
+                if !self.remove_breakpoint(line) {
+                    writeln!(output, "No breakpoint at {}", line)?;
+                }
+            }
+            Command::Watch(register) if register >= N => {
+                writeln!(output, "No register {}", register)?;
+            }
+            Command::Watch(register) => {
+                self.add_watchpoint(register);
+                writeln!(output, "Watching r{}", register)?;
+            }
+            Command::Unwatch(register) => {
+                if !self.remove_watchpoint(register) {
+                    writeln!(output, "r{} is not watched", register)?;
+                }
+            }
+            Command::Set(register, _) if register >= N => {
+                writeln!(output, "No register {}", register)?;
+            }
+            Command::Set(register, value) => {
+                self.program.registers[register] = value;
+                self.write_registers(output)?;
+            }
+            Command::Registers => self.write_registers(output)?,
+            Command::List(count) => self.write_listing(count, output)?,
+            Command::Info => {
+                writeln!(output, "Breakpoints: {:?}", self.breakpoints)?;
+                writeln!(output, "Watchpoints: {:?}", self.watchpoints)?;
+            }
+            Command::Help => writeln!(output, "{}", HELP)?,
+            Command::Quit => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Reads commands from `input` until it ends or the quit command
+    pub fn repl<B: BufRead, W: Write>(&mut self, input: B, mut output: W) -> io::Result<()> {
+        let mut previous = None;
+        let mut lines = input.lines();
+        loop {
+            write!(output, "{}", PROMPT)?;
+            output.flush()?;
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => return Ok(()),
+            };
+            let command = if line.trim().is_empty() {
+                match previous.take() {
+                    Some(command) => command,
+                    None => continue,
+                }
+            } else {
+                match line.parse::<Command<R>>() {
+                    Ok(command) => command,
+                    Err(e) => {
+                        writeln!(output, "{}", e)?;
+                        continue;
+                    }
+                }
+            };
+            if !self.execute(&command, &mut output)? {
+                return Ok(());
+            }
+            previous = Some(command);
+        }
+    }
+
+    fn write_stop<W: Write>(&self, stop: Stop<R>, output: &mut W) -> io::Result<()> {
+        match stop {
+            Stop::Stepped => {}
+            Stop::Breakpoint(line) => writeln!(output, "Breakpoint at {}", line)?,
+            Stop::Watchpoint { register, old, new } => {
+                writeln!(output, "r{} changed from {} to {}", register, old, new)?
+            }
+            Stop::Halted => writeln!(
+                output,
+                "Halted after {} instructions",
+                self.program.instructions_executed()
+            )?,
+        }
+        self.write_registers(output)
+    }
+
+    fn write_registers<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let ip = self.program.ip();
+        write!(output, "ip={} {:?}", ip, self.program.registers)?;
+        match self.program.current_instruction() {
+            Some(instruction) => writeln!(output, " {}", instruction),
+            None => writeln!(output),
+        }
+    }
+
+    fn write_listing<W: Write>(&self, count: usize, output: &mut W) -> io::Result<()> {
+        let ip = self.program.ip();
+        for (line, instruction) in self
+            .program
+            .instructions
+            .iter()
+            .enumerate()
+            .skip(ip)
+            .take(count)
+        {
+            let marker = if line == ip { "=>" } else { "  " };
+            let breakpoint = if self.breakpoints.contains(&line) {
+                "*"
+            } else {
+                " "
+            };
+            writeln!(output, "{}{}{:3} {}", marker, breakpoint, line, instruction)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_INPUT: &str =
+        "#ip 0\nseti 5 0 1\nseti 6 0 2\naddi 0 1 0\naddr 1 2 3\nsetr 1 0 0\nseti 8 0 4\nseti 9 0 5";
+
+    fn sample_debugger() -> Debugger<u32, 6> {
+        Debugger::new(SAMPLE_INPUT.parse().unwrap())
+    }
+
+    #[test]
+    fn it_parses_commands() {
+        assert_eq!("s".parse(), Ok(Command::<u32>::Step(1)));
+        assert_eq!("step 10".parse(), Ok(Command::<u32>::Step(10)));
+        assert_eq!(" b 28 ".parse(), Ok(Command::<u32>::Break(28)));
+        assert_eq!("set 0 42".parse(), Ok(Command::<u32>::Set(0, 42)));
+        assert_eq!("l".parse(), Ok(Command::<u32>::List(5)));
+        assert!("b".parse::<Command<u32>>().is_err());
+        assert!("b 1 2".parse::<Command<u32>>().is_err());
+        assert!("set 0 -1".parse::<Command<u32>>().is_err());
+        assert!("jump 3".parse::<Command<u32>>().is_err());
+    }
+
+    #[test]
+    fn it_stops_at_breakpoints_and_watchpoints() {
+        let mut debugger = sample_debugger();
+        debugger.add_breakpoint(6);
+        assert_eq!(debugger.resume(), Stop::Breakpoint(6));
+        assert_eq!(debugger.program.registers, [6, 5, 6, 0, 0, 0]);
+        // Resuming always executes the instruction at the breakpoint first
+        assert_eq!(debugger.resume(), Stop::Halted);
+
+        let mut debugger = sample_debugger();
+        debugger.add_watchpoint(2);
+        assert_eq!(debugger.step(1), Stop::Stepped);
+        assert_eq!(
+            debugger.step(5),
+            Stop::Watchpoint {
+                register: 2,
+                old: 0,
+                new: 6
+            }
+        );
+        assert_eq!(debugger.program.ip(), 2);
+    }
+
+    #[test]
+    fn it_stops_stepping_at_breakpoints() {
+        let mut debugger = sample_debugger();
+        debugger.add_breakpoint(4);
+        assert_eq!(debugger.step(10), Stop::Breakpoint(4));
+        assert_eq!(debugger.program.registers, [4, 5, 6, 0, 0, 0]);
+        // A breakpoint reached by the last step isn't reported
+        let mut debugger = sample_debugger();
+        debugger.add_breakpoint(2);
+        assert_eq!(debugger.step(2), Stop::Stepped);
+        assert_eq!(debugger.program.ip(), 2);
+        assert_eq!(debugger.step(1), Stop::Stepped);
+    }
+
+    #[test]
+    fn it_runs_scripted_sessions() {
+        let script = "b 6\nc\n\nfoo\nset 3 7\nw 9\nl 2\nq\nc\n";
+        let mut output = vec![];
+        sample_debugger()
+            .repl(script.as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let expected = [
+            "Breakpoint at 6",
+            "Breakpoint at 6",
+            "ip=6 [6, 5, 6, 0, 0, 0] seti 9 0 5",
+            "Halted after 5 instructions",
+            "ip=7 [7, 5, 6, 0, 0, 9]",
+            "Unknown command: foo, try help",
+            "ip=7 [7, 5, 6, 7, 0, 9]",
+            "No register 9",
+            "",
+        ];
+        assert_eq!(output.replace(PROMPT, ""), expected.join("\n"));
+    }
+}
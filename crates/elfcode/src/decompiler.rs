@@ -0,0 +1,446 @@
+use crate::{Instruction, Op, Program, Word};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// A value read by an instruction, reads of the `#ip` register are replaced by the line number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand<R> {
+    Register(usize),
+    Value(R),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Mul,
+    And,
+    Or,
+    Gt,
+    Eq,
+}
+
+impl BinaryOp {
+    pub fn apply<R: Word>(self, a: R, b: R) -> R {
+        match self {
+            BinaryOp::Add => a.wrapping_add(b),
+            BinaryOp::Mul => a.wrapping_mul(b),
+            BinaryOp::And => a.bitand(b),
+            BinaryOp::Or => a.bitor(b),
+            BinaryOp::Gt => R::from_bool(a > b),
+            BinaryOp::Eq => R::from_bool(a == b),
+        }
+    }
+
+    pub fn is_comparison(self) -> bool {
+        self == BinaryOp::Gt || self == BinaryOp::Eq
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Mul => "*",
+            BinaryOp::And => "&",
+            BinaryOp::Or => "|",
+            BinaryOp::Gt => ">",
+            BinaryOp::Eq => "==",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expr<R> {
+    Operand(Operand<R>),
+    Binary(Operand<R>, BinaryOp, Operand<R>),
+}
+
+impl<R: Word> Expr<R> {
+    /// The value of the expression if it doesn't read any registers
+    pub fn constant(&self) -> Option<R> {
+        match *self {
+            Expr::Operand(Operand::Value(v)) => Some(v),
+            Expr::Binary(Operand::Value(a), op, Operand::Value(b)) => Some(op.apply(a, b)),
+            _ => None,
+        }
+    }
+
+    pub fn is_comparison(&self) -> bool {
+        matches!(*self, Expr::Binary(_, op, _) if op.is_comparison())
+    }
+
+    pub fn reads(&self, register: usize) -> bool {
+        let operand = Operand::Register(register);
+        match *self {
+            Expr::Operand(a) => a == operand,
+            Expr::Binary(a, _, b) => a == operand || b == operand,
+        }
+    }
+}
+
+/// An instruction with its jumps made explicit, line numbers are instruction indices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Statement<R> {
+    Assign(usize, Expr<R>),
+    /// Jumps to the line, a line past the end of the program halts it
+    Goto(usize),
+    /// Jumps to the line if the condition holds, continues with the next line otherwise
+    If(Expr<R>, usize),
+    /// Jumps to one past the value of the expression
+    ComputedGoto(Expr<R>),
+}
+
+impl<R: Word> Statement<R> {
+    /// The line this statement jumps to, when it is known without running the program
+    pub fn target(&self) -> Option<usize> {
+        match *self {
+            Statement::Goto(target) | Statement::If(_, target) => Some(target),
+            _ => None,
+        }
+    }
+}
+
+/// A run of statements only entered at its first line and only left from its last
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub start: usize,
+    /// Exclusive
+    pub end: usize,
+    /// Lines that jump back to the start of the block, making it the head of a loop
+    pub loop_ends: Vec<usize>,
+}
+
+/// An equality check between two registers that halts the program when it holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HaltCheck {
+    /// The line of the comparison
+    pub line: usize,
+    /// The register the checked register is compared with
+    pub register: usize,
+}
+
+/// A program turned into pseudo-code, with registers named `a`, `b`, ... and the `#ip`
+/// register named `ip`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decompiled<R> {
+    pub statements: Vec<Statement<R>>,
+    pub blocks: Vec<Block>,
+    pub ip_register: Option<usize>,
+}
+
+impl<R: Word> Decompiled<R> {
+    pub fn new<const N: usize>(program: &Program<R, N>) -> Self {
+        let statements: Vec<_> = (0..program.instructions.len())
+            .map(|line| statement(program, line))
+            .collect();
+        let blocks = find_blocks(&statements);
+        Self {
+            statements,
+            blocks,
+            ip_register: program.ip_register,
+        }
+    }
+
+    /// Finds the comparison that halts the program when `register` equals another register
+    pub fn find_halt_check(&self, register: usize) -> Option<HaltCheck> {
+        (1..self.statements.len()).find_map(|line| {
+            match self.statements[line] {
+                Statement::If(_, target) if self.halts_from(target) => {}
+                _ => return None,
+            }
+            let other = match self.statements[line - 1] {
+                Statement::Assign(
+                    _,
+                    Expr::Binary(Operand::Register(a), BinaryOp::Eq, Operand::Register(b)),
+                ) if a == register => b,
+                Statement::Assign(
+                    _,
+                    Expr::Binary(Operand::Register(a), BinaryOp::Eq, Operand::Register(b)),
+                ) if b == register => a,
+                _ => return None,
+            };
+            Some(HaltCheck {
+                line: line - 1,
+                register: other,
+            })
+            .filter(|_| other != register)
+        })
+    }
+
+    /// Whether execution from `line` reaches the end of the program with nothing but jumps
+    fn halts_from(&self, mut line: usize) -> bool {
+        for _ in 0..=self.statements.len() {
+            match self.statements.get(line) {
+                None => return true,
+                Some(&Statement::Goto(target)) => line = target,
+                Some(_) => return false,
+            }
+        }
+        false
+    }
+
+    pub fn register_name(&self, register: usize) -> String {
+        if Some(register) == self.ip_register {
+            String::from("ip")
+        } else {
+            char::from(b'a' + register as u8).to_string()
+        }
+    }
+
+    fn operand(&self, operand: Operand<R>) -> String {
+        match operand {
+            Operand::Register(r) => self.register_name(r),
+            Operand::Value(v) => v.to_string(),
+        }
+    }
+
+    fn expr(&self, expr: &Expr<R>) -> String {
+        match *expr {
+            Expr::Operand(operand) => self.operand(operand),
+            Expr::Binary(a, op, b) => {
+                format!("{} {} {}", self.operand(a), op.symbol(), self.operand(b))
+            }
+        }
+    }
+
+    fn label(&self, line: usize) -> String {
+        if line >= self.statements.len() {
+            String::from("end")
+        } else {
+            format!("L{}", line)
+        }
+    }
+
+    fn statement(&self, statement: &Statement<R>) -> String {
+        match *statement {
+            Statement::Assign(r, ref expr) => {
+                format!("{} = {}", self.register_name(r), self.expr(expr))
+            }
+            Statement::Goto(target) if target >= self.statements.len() => String::from("halt"),
+            Statement::Goto(target) => format!("goto {}", self.label(target)),
+            Statement::If(ref condition, target) if target >= self.statements.len() => {
+                format!("if {} halt", self.expr(condition))
+            }
+            Statement::If(ref condition, target) => {
+                format!("if {} goto {}", self.expr(condition), self.label(target))
+            }
+            // A jump table, the usual form adds a register to the current line
+            Statement::ComputedGoto(Expr::Binary(Operand::Value(base), BinaryOp::Add, offset))
+            | Statement::ComputedGoto(Expr::Binary(offset, BinaryOp::Add, Operand::Value(base))) => {
+                format!(
+                    "goto L{} + {}",
+                    base.to_usize().saturating_add(1),
+                    self.operand(offset)
+                )
+            }
+            Statement::ComputedGoto(ref expr) => format!("goto ({}) + 1", self.expr(expr)),
+        }
+    }
+}
+
+impl<R: Word> fmt::Display for Decompiled<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, block) in self.blocks.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}:", self.label(block.start))?;
+            if !block.loop_ends.is_empty() {
+                let ends: Vec<_> = block.loop_ends.iter().map(|l| self.label(*l)).collect();
+                write!(f, "  // loop, repeated from {}", ends.join(", "))?;
+            }
+            writeln!(f)?;
+            for line in block.start..block.end {
+                writeln!(f, "{:>6}  {}", line, self.statement(&self.statements[line]))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Decompiles the instruction at `line`
+fn statement<R: Word, const N: usize>(program: &Program<R, N>, line: usize) -> Statement<R> {
+    let instruction = &program.instructions[line];
+    let expr = expression(instruction, |v| {
+        if Some(v.to_usize()) == program.ip_register {
+            Operand::Value(R::from_usize(line))
+        } else {
+            Operand::Register(v.to_usize())
+        }
+    });
+    if Some(instruction.output_register()) != program.ip_register {
+        return Statement::Assign(instruction.output_register(), expr);
+    }
+    if let Some(value) = expr.constant() {
+        return Statement::Goto(value.to_usize().saturating_add(1));
+    }
+    // Adding the result of a comparison to ip skips the next line when it holds
+    if let Expr::Binary(a, BinaryOp::Add, b) = expr {
+        let condition = match (a, b) {
+            (Operand::Register(r), Operand::Value(v))
+            | (Operand::Value(v), Operand::Register(r))
+                if v.to_usize() == line && line > 0 =>
+            {
+                match statement(program, line - 1) {
+                    // When the comparison overwrote one of its operands only the result is left
+                    Statement::Assign(written, condition)
+                        if written == r && condition.is_comparison() =>
+                    {
+                        if condition.reads(written) {
+                            Some(Expr::Operand(Operand::Register(written)))
+                        } else {
+                            Some(condition)
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        if let Some(condition) = condition {
+            return Statement::If(condition, line + 2);
+        }
+    }
+    Statement::ComputedGoto(expr)
+}
+
+/// The expression computed by an instruction, `register` gives the operand for register reads
+#[rustfmt::skip]
+fn expression<R: Word, F>(instruction: &Instruction<R>, register: F) -> Expr<R>
+where
+    F: Fn(R) -> Operand<R>,
+{
+    let Instruction { op, a, b, .. } = *instruction;
+    let value = Operand::Value;
+    match op {
+        Op::Addr => Expr::Binary(register(a), BinaryOp::Add, register(b)),
+        Op::Addi => Expr::Binary(register(a), BinaryOp::Add, value(b)),
+        Op::Mulr => Expr::Binary(register(a), BinaryOp::Mul, register(b)),
+        Op::Muli => Expr::Binary(register(a), BinaryOp::Mul, value(b)),
+        Op::Banr => Expr::Binary(register(a), BinaryOp::And, register(b)),
+        Op::Bani => Expr::Binary(register(a), BinaryOp::And, value(b)),
+        Op::Borr => Expr::Binary(register(a), BinaryOp::Or, register(b)),
+        Op::Bori => Expr::Binary(register(a), BinaryOp::Or, value(b)),
+        Op::Setr => Expr::Operand(register(a)),
+        Op::Seti => Expr::Operand(value(a)),
+        Op::Gtir => Expr::Binary(value(a), BinaryOp::Gt, register(b)),
+        Op::Gtri => Expr::Binary(register(a), BinaryOp::Gt, value(b)),
+        Op::Gtrr => Expr::Binary(register(a), BinaryOp::Gt, register(b)),
+        Op::Eqir => Expr::Binary(value(a), BinaryOp::Eq, register(b)),
+        Op::Eqri => Expr::Binary(register(a), BinaryOp::Eq, value(b)),
+        Op::Eqrr => Expr::Binary(register(a), BinaryOp::Eq, register(b)),
+    }
+}
+
+/// Splits the statements into basic blocks, starting new blocks at jump targets and after jumps
+fn find_blocks<R: Word>(statements: &[Statement<R>]) -> Vec<Block> {
+    let mut leaders = BTreeSet::new();
+    let mut back_edges: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    leaders.insert(0);
+    for (line, statement) in statements.iter().enumerate() {
+        if let Statement::Assign(..) = statement {
+            continue;
+        }
+        leaders.insert(line + 1);
+        if let Some(target) = statement.target() {
+            leaders.insert(target);
+            if target <= line {
+                back_edges.entry(target).or_default().push(line);
+            }
+        }
+    }
+    let leaders: Vec<_> = leaders
+        .into_iter()
+        .filter(|&l| l < statements.len())
+        .collect();
+    leaders
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| Block {
+            start,
+            end: leaders.get(i + 1).cloned().unwrap_or(statements.len()),
+            loop_ends: back_edges.remove(&start).unwrap_or_default(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_decompiles_jumps_and_conditions() {
+        let program: Program<u32, 6> = "#ip 4\nseti 123 0 2\nbani 2 456 2\neqri 2 72 3\naddr 3 4 4\nseti 0 0 4\naddr 1 4 4\nmulr 4 4 4"
+            .parse()
+            .unwrap();
+        let decompiled = Decompiled::new(&program);
+        assert_eq!(
+            decompiled.statements,
+            vec![
+                Statement::Assign(2, Expr::Operand(Operand::Value(123))),
+                Statement::Assign(
+                    2,
+                    Expr::Binary(Operand::Register(2), BinaryOp::And, Operand::Value(456))
+                ),
+                Statement::Assign(
+                    3,
+                    Expr::Binary(Operand::Register(2), BinaryOp::Eq, Operand::Value(72))
+                ),
+                Statement::If(
+                    Expr::Binary(Operand::Register(2), BinaryOp::Eq, Operand::Value(72)),
+                    5
+                ),
+                Statement::Goto(1),
+                Statement::ComputedGoto(Expr::Binary(
+                    Operand::Register(1),
+                    BinaryOp::Add,
+                    Operand::Value(5)
+                )),
+                Statement::Goto(37),
+            ]
+        );
+        let blocks: Vec<_> = decompiled.blocks.iter().map(|b| (b.start, b.end)).collect();
+        assert_eq!(blocks, vec![(0, 1), (1, 4), (4, 5), (5, 6), (6, 7)]);
+        assert_eq!(decompiled.blocks[1].loop_ends, vec![4]);
+    }
+
+    #[test]
+    fn it_formats_pseudo_code() {
+        let program: Program<u32, 6> = "#ip 4\nseti 123 0 2\nbani 2 456 2\neqri 2 72 2\naddr 2 4 4\nseti 0 0 4\naddr 1 4 4\nmulr 4 4 4"
+            .parse()
+            .unwrap();
+        let expected = "L0:
+     0  c = 123
+
+L1:  // loop, repeated from L4
+     1  c = c & 456
+     2  c = c == 72
+     3  if c goto L5
+
+L4:
+     4  goto L1
+
+L5:
+     5  goto L6 + b
+
+L6:
+     6  halt
+";
+        assert_eq!(Decompiled::new(&program).to_string(), expected);
+    }
+
+    #[test]
+    fn it_finds_halt_checks() {
+        let program: Program<u32, 6> =
+            "#ip 4\nseti 5 0 2\neqrr 0 2 3\naddr 3 4 4\nseti 0 0 4\nseti 4 0 4\nseti 8 0 4"
+                .parse()
+                .unwrap();
+        let decompiled = Decompiled::new(&program);
+        assert_eq!(
+            decompiled.find_halt_check(0),
+            Some(HaltCheck {
+                line: 1,
+                register: 2
+            })
+        );
+        assert_eq!(decompiled.find_halt_check(1), None);
+    }
+}
@@ -0,0 +1,17 @@
+//! The ElfCode virtual machine from days 16, 19 and 21
+
+mod debugger;
+mod decompiler;
+mod op;
+mod optimizer;
+mod program;
+mod tracer;
+mod word;
+
+pub use crate::debugger::{Command, Debugger, Stop};
+pub use crate::decompiler::{BinaryOp, Block, Decompiled, Expr, HaltCheck, Operand, Statement};
+pub use crate::op::{Instruction, Op};
+pub use crate::optimizer::{Idiom, Shortcut};
+pub use crate::program::Program;
+pub use crate::tracer::{TraceEntry, Tracer};
+pub use crate::word::Word;
@@ -0,0 +1,249 @@
+use crate::Word;
+use common::{Cursor, Line, ParseError, ParseResult};
+use std::fmt;
+use std::str::FromStr;
+
+/// The 16 operations, `r` operands are registers and `i` operands immediate values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Op {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+impl Op {
+    pub const ALL: [Op; 16] = [
+        Op::Addr,
+        Op::Addi,
+        Op::Mulr,
+        Op::Muli,
+        Op::Banr,
+        Op::Bani,
+        Op::Borr,
+        Op::Bori,
+        Op::Setr,
+        Op::Seti,
+        Op::Gtir,
+        Op::Gtri,
+        Op::Gtrr,
+        Op::Eqir,
+        Op::Eqri,
+        Op::Eqrr,
+    ];
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Op::Addr => "addr",
+            Op::Addi => "addi",
+            Op::Mulr => "mulr",
+            Op::Muli => "muli",
+            Op::Banr => "banr",
+            Op::Bani => "bani",
+            Op::Borr => "borr",
+            Op::Bori => "bori",
+            Op::Setr => "setr",
+            Op::Seti => "seti",
+            Op::Gtir => "gtir",
+            Op::Gtri => "gtri",
+            Op::Gtrr => "gtrr",
+            Op::Eqir => "eqir",
+            Op::Eqri => "eqri",
+            Op::Eqrr => "eqrr",
+        }
+    }
+
+    /// Whether each of the operands `a`, `b` and `c` names a register
+    pub fn register_operands(self) -> [bool; 3] {
+        match self {
+            Op::Addr | Op::Mulr | Op::Banr | Op::Borr | Op::Gtrr | Op::Eqrr => [true, true, true],
+            Op::Addi | Op::Muli | Op::Bani | Op::Bori | Op::Setr | Op::Gtri | Op::Eqri => {
+                [true, false, true]
+            }
+            Op::Gtir | Op::Eqir => [false, true, true],
+            Op::Seti => [false, false, true],
+        }
+    }
+
+    pub fn from_mnemonic(name: &str) -> Option<Self> {
+        Op::ALL.iter().find(|op| op.mnemonic() == name).cloned()
+    }
+
+    /// Reads the op name at the cursor
+    pub(crate) fn read(cursor: &mut Cursor) -> ParseResult<Self> {
+        let start = *cursor;
+        Op::from_mnemonic(cursor.word()).ok_or_else(|| start.error("an op name"))
+    }
+
+    /// Stores the result of the operation in register `c`
+    ///
+    /// Panics if a register operand is not a register.
+    #[rustfmt::skip]
+    pub fn execute<R: Word, const N: usize>(self, a: R, b: R, c: R, registers: &mut [R; N]) {
+        let reg = |v: R| registers[v.to_usize()];
+        registers[c.to_usize()] = match self {
+            Op::Addr => reg(a).wrapping_add(reg(b)),
+            Op::Addi => reg(a).wrapping_add(b),
+            Op::Mulr => reg(a).wrapping_mul(reg(b)),
+            Op::Muli => reg(a).wrapping_mul(b),
+            Op::Banr => reg(a).bitand(reg(b)),
+            Op::Bani => reg(a).bitand(b),
+            Op::Borr => reg(a).bitor(reg(b)),
+            Op::Bori => reg(a).bitor(b),
+            Op::Setr => reg(a),
+            Op::Seti => a,
+            Op::Gtir => R::from_bool(a > reg(b)),
+            Op::Gtri => R::from_bool(reg(a) > b),
+            Op::Gtrr => R::from_bool(reg(a) > reg(b)),
+            Op::Eqir => R::from_bool(a == reg(b)),
+            Op::Eqri => R::from_bool(reg(a) == b),
+            Op::Eqrr => R::from_bool(reg(a) == reg(b)),
+        };
+    }
+}
+
+impl FromStr for Op {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = single_line(s).cursor();
+        let op = Op::read(&mut cursor)?;
+        cursor.end()?;
+        Ok(op)
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.mnemonic())
+    }
+}
+
+/// An op with its three operands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction<R> {
+    pub op: Op,
+    pub a: R,
+    pub b: R,
+    pub c: R,
+}
+
+impl<R: Word> Instruction<R> {
+    pub fn new(op: Op, a: R, b: R, c: R) -> Self {
+        Self { op, a, b, c }
+    }
+
+    pub fn execute<const N: usize>(&self, registers: &mut [R; N]) {
+        self.op.execute(self.a, self.b, self.c, registers);
+    }
+
+    /// The register the result is written to
+    pub fn output_register(&self) -> usize {
+        self.c.to_usize()
+    }
+}
+
+impl<R: Word> Instruction<R> {
+    /// Parses an instruction written as its mnemonic followed by the operands, like
+    /// `addi 1 2 3`, from a line of a program with the given number of registers
+    pub fn from_line(line: &Line, registers: usize) -> ParseResult<Self> {
+        let mut cursor = line.cursor();
+        cursor.skip_whitespace();
+        let op = Op::read(&mut cursor)?;
+        Self::read_operands(op, &mut cursor, registers)
+    }
+
+    /// Reads the three operands after the op, which must end the line, checking that the
+    /// register operands are below `registers`
+    pub(crate) fn read_operands(
+        op: Op,
+        cursor: &mut Cursor,
+        registers: usize,
+    ) -> ParseResult<Self> {
+        let mut operands = [R::ZERO; 3];
+        for (operand, &is_register) in operands.iter_mut().zip(op.register_operands().iter()) {
+            cursor.skip_whitespace();
+            let start = *cursor;
+            *operand = cursor.number("an operand")?;
+            if is_register && operand.to_usize() >= registers {
+                return Err(start.error(format!("a register below {}", registers)));
+            }
+        }
+        cursor.end()?;
+        let [a, b, c] = operands;
+        Ok(Self::new(op, a, b, c))
+    }
+}
+
+/// Parses an instruction without a program, so any register operand is accepted
+impl<R: Word> FromStr for Instruction<R> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_line(&single_line(s), usize::MAX)
+    }
+}
+
+/// `s` as the first line of a program
+fn single_line(s: &str) -> Line<'_> {
+    Line { number: 1, text: s }
+}
+
+impl<R: Word> fmt::Display for Instruction<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.op, self.a, self.b, self.c)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_executes_ops() {
+        // The example from day 16, which behaves like mulr, addi and seti
+        let before = [3u32, 2, 1, 1];
+        let after = [3, 2, 2, 1];
+        let matching: Vec<_> = Op::ALL
+            .iter()
+            .filter(|op| {
+                let mut registers = before;
+                op.execute(2, 1, 2, &mut registers);
+                registers == after
+            })
+            .collect();
+        assert_eq!(matching, vec![&Op::Addi, &Op::Mulr, &Op::Seti]);
+    }
+
+    #[test]
+    fn it_parses_and_formats_instructions() {
+        let instruction: Instruction<u64> = "bani 2 456 2".parse().unwrap();
+        assert_eq!(instruction, Instruction::new(Op::Bani, 2, 456, 2));
+        assert_eq!(instruction.to_string(), "bani 2 456 2");
+        assert!("band 2 456 2".parse::<Instruction<u64>>().is_err());
+        assert!("bani 2 456".parse::<Instruction<u64>>().is_err());
+        assert_eq!(
+            "bani 2 456 2".parse::<Instruction<u8>>().unwrap_err(),
+            ParseError::new(1, 8, "an operand", "456")
+        );
+        assert_eq!("eqrr".parse(), Ok(Op::Eqrr));
+        let line = single_line("gtir 9 4 3");
+        assert!(Instruction::<u8>::from_line(&line, 5).is_ok());
+        assert_eq!(
+            Instruction::<u8>::from_line(&line, 4).unwrap_err(),
+            ParseError::new(1, 8, "a register below 4", "4")
+        );
+    }
+}
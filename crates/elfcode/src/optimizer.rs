@@ -0,0 +1,357 @@
+use crate::{BinaryOp, Decompiled, Expr, Operand, Statement, Word};
+
+/// A known loop idiom that can be computed natively instead of instruction by instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Idiom<R> {
+    /// Adds every divisor of `number` to `sum` by trying every pair of factors
+    SumOfDivisors {
+        sum: usize,
+        number: usize,
+        outer: usize,
+        inner: usize,
+        scratch: usize,
+    },
+    /// Divides `dividend` by `divisor` by counting up until the next multiple is too large
+    Division {
+        quotient: usize,
+        dividend: usize,
+        divisor: R,
+        scratch: usize,
+    },
+}
+
+/// An idiom found in a program, taken when the instruction pointer reaches `start`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shortcut<R> {
+    pub idiom: Idiom<R>,
+    pub start: usize,
+    /// Exclusive end of the lines the idiom covers
+    pub end: usize,
+    /// The line execution continues at after the idiom
+    pub exit: usize,
+}
+
+impl<R: Word> Shortcut<R> {
+    /// Leaves the registers, other than the instruction pointer, as the loop would have
+    pub fn apply<const N: usize>(&self, registers: &mut [R; N]) {
+        match self.idiom {
+            Idiom::SumOfDivisors {
+                sum,
+                number,
+                outer,
+                inner,
+                scratch,
+            } => {
+                let n = registers[number].to_usize();
+                let divisors = R::from_usize(sum_of_divisors(n));
+                registers[sum] = registers[sum].wrapping_add(divisors);
+                registers[outer] = R::from_usize(n.max(1) + 1);
+                registers[inner] = registers[outer];
+                registers[scratch] = R::ONE;
+            }
+            Idiom::Division {
+                quotient,
+                dividend,
+                divisor,
+                scratch,
+            } => {
+                let quotient_value = registers[dividend].to_usize() / divisor.to_usize();
+                registers[quotient] = R::from_usize(quotient_value);
+                registers[scratch] = R::ONE;
+            }
+        }
+    }
+}
+
+fn sum_of_divisors(n: usize) -> usize {
+    (1..)
+        .take_while(|i| i * i <= n)
+        .filter(|i| n.is_multiple_of(*i))
+        .map(|i| if i * i == n { i } else { i + n / i })
+        .sum()
+}
+
+/// Finds every known idiom in the program
+pub fn find_shortcuts<R: Word>(decompiled: &Decompiled<R>) -> Vec<Shortcut<R>> {
+    let statements = &decompiled.statements;
+    (0..statements.len())
+        .filter_map(|start| {
+            sum_of_divisors_at(statements, start).or_else(|| division_at(statements, start))
+        })
+        .collect()
+}
+
+/// Matches the nested loop from day 19, registers can differ
+///
+/// ```text
+/// c = 1
+/// b = 1
+/// d = c * b        <-+ <-+
+/// d = d == e         |   |
+/// if d: a = c + a    |   |
+/// b = b + 1          |   |
+/// if b <= e goto ----+   |
+/// c = c + 1              |
+/// if c <= e goto --------+
+/// ```
+fn sum_of_divisors_at<R: Word>(statements: &[Statement<R>], start: usize) -> Option<Shortcut<R>> {
+    let at = |offset: usize| statements.get(start + offset);
+    let outer = assigned_value(at(0)?, R::ONE)?;
+    let inner = assigned_value(at(1)?, R::ONE)?;
+    let (scratch, product) = assignment(at(2)?)?;
+    if !is_binary(&product, outer, BinaryOp::Mul, Operand::Register(inner)) {
+        return None;
+    }
+    let (comparison, equals) = assignment(at(3)?)?;
+    let number = other_register(&equals, BinaryOp::Eq, scratch)?;
+    let (sum, addition) = assignment(at(6)?)?;
+    let registers = [sum, number, outer, inner, scratch];
+    let distinct = (1..registers.len()).all(|i| !registers[..i].contains(&registers[i]));
+    let matches = distinct
+        && comparison == scratch
+        && is_skip(at(4)?, start + 6)
+        && *at(5)? == Statement::Goto(start + 7)
+        && is_binary(&addition, sum, BinaryOp::Add, Operand::Register(outer))
+        && is_increment(at(7)?, inner)
+        && is_greater_than(at(8)?, scratch, inner, number)
+        && is_skip(at(9)?, start + 11)
+        && *at(10)? == Statement::Goto(start + 2)
+        && is_increment(at(11)?, outer)
+        && is_greater_than(at(12)?, scratch, outer, number)
+        && is_skip(at(13)?, start + 15)
+        && *at(14)? == Statement::Goto(start + 1);
+    if !matches {
+        return None;
+    }
+    Some(Shortcut {
+        idiom: Idiom::SumOfDivisors {
+            sum,
+            number,
+            outer,
+            inner,
+            scratch,
+        },
+        start,
+        end: start + 15,
+        exit: start + 15,
+    })
+}
+
+/// Matches the loop from day 21 dividing by 256, registers and the divisor can differ
+///
+/// ```text
+/// d = 0
+/// b = d + 1        <-+
+/// b = b * 256        |
+/// if b > f goto exit |
+/// d = d + 1          |
+/// goto --------------+
+/// ```
+fn division_at<R: Word>(statements: &[Statement<R>], start: usize) -> Option<Shortcut<R>> {
+    let at = |offset: usize| statements.get(start + offset);
+    let quotient = assigned_value(at(0)?, R::ZERO)?;
+    let (scratch, next) = assignment(at(1)?)?;
+    if !is_binary(&next, quotient, BinaryOp::Add, Operand::Value(R::ONE)) {
+        return None;
+    }
+    let (multiplied, multiple) = assignment(at(2)?)?;
+    let divisor = match other_operand(&multiple, BinaryOp::Mul, scratch)? {
+        Operand::Value(divisor) if divisor != R::ZERO => divisor,
+        _ => return None,
+    };
+    let (compared, comparison) = assignment(at(3)?)?;
+    let dividend = match comparison {
+        Expr::Binary(Operand::Register(a), BinaryOp::Gt, Operand::Register(b)) if a == scratch => b,
+        _ => return None,
+    };
+    let exit = match *at(6)? {
+        Statement::Goto(exit) => exit,
+        _ => return None,
+    };
+    let matches = quotient != scratch
+        && dividend != scratch
+        && dividend != quotient
+        && multiplied == scratch
+        && compared == scratch
+        && is_skip(at(4)?, start + 6)
+        && *at(5)? == Statement::Goto(start + 7)
+        && is_increment(at(7)?, quotient)
+        && *at(8)? == Statement::Goto(start + 1);
+    if !matches {
+        return None;
+    }
+    Some(Shortcut {
+        idiom: Idiom::Division {
+            quotient,
+            dividend,
+            divisor,
+            scratch,
+        },
+        start,
+        end: start + 9,
+        exit,
+    })
+}
+
+fn assignment<R: Word>(statement: &Statement<R>) -> Option<(usize, Expr<R>)> {
+    match *statement {
+        Statement::Assign(register, expr) => Some((register, expr)),
+        _ => None,
+    }
+}
+
+/// The register the statement sets to `value`
+fn assigned_value<R: Word>(statement: &Statement<R>, value: R) -> Option<usize> {
+    match *statement {
+        Statement::Assign(register, Expr::Operand(Operand::Value(v))) if v == value => {
+            Some(register)
+        }
+        _ => None,
+    }
+}
+
+fn is_commutative(op: BinaryOp) -> bool {
+    op != BinaryOp::Gt
+}
+
+/// The operand combined with `register` by `op`, in either order for commutative ops
+fn other_operand<R: Word>(expr: &Expr<R>, op: BinaryOp, register: usize) -> Option<Operand<R>> {
+    match *expr {
+        Expr::Binary(Operand::Register(a), o, b) if o == op && a == register => Some(b),
+        Expr::Binary(a, o, Operand::Register(b))
+            if o == op && b == register && is_commutative(op) =>
+        {
+            Some(a)
+        }
+        _ => None,
+    }
+}
+
+fn other_register<R: Word>(expr: &Expr<R>, op: BinaryOp, register: usize) -> Option<usize> {
+    match other_operand(expr, op, register)? {
+        Operand::Register(other) => Some(other),
+        Operand::Value(_) => None,
+    }
+}
+
+fn is_binary<R: Word>(expr: &Expr<R>, register: usize, op: BinaryOp, other: Operand<R>) -> bool {
+    other_operand(expr, op, register) == Some(other)
+}
+
+fn is_increment<R: Word>(statement: &Statement<R>, register: usize) -> bool {
+    match assignment(statement) {
+        Some((r, expr)) => {
+            r == register && is_binary(&expr, r, BinaryOp::Add, Operand::Value(R::ONE))
+        }
+        None => false,
+    }
+}
+
+/// Whether the statement is `output = a > b`
+fn is_greater_than<R: Word>(statement: &Statement<R>, output: usize, a: usize, b: usize) -> bool {
+    *statement
+        == Statement::Assign(
+            output,
+            Expr::Binary(Operand::Register(a), BinaryOp::Gt, Operand::Register(b)),
+        )
+}
+
+/// Whether the statement skips the next line when the previous comparison holds
+fn is_skip<R: Word>(statement: &Statement<R>, target: usize) -> bool {
+    matches!(*statement, Statement::If(_, t) if t == target)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Program;
+
+    // The nested loop from day 19 after setting e
+    const SUM_OF_DIVISORS: &str = "#ip 5
+seti 0 0 4
+seti 1 1 2
+seti 1 8 1
+mulr 2 1 3
+eqrr 3 4 3
+addr 3 5 5
+addi 5 1 5
+addr 2 0 0
+addi 1 1 1
+gtrr 1 4 3
+addr 5 3 5
+seti 2 6 5
+addi 2 1 2
+gtrr 2 4 3
+addr 3 5 5
+seti 1 2 5
+mulr 5 5 5";
+
+    // The division loop from day 21 after setting f
+    const DIVISION: &str = "#ip 4
+seti 0 0 5
+seti 0 8 3
+addi 3 1 1
+muli 1 256 1
+gtrr 1 5 1
+addr 1 4 4
+addi 4 1 4
+seti 9 3 4
+addi 3 1 3
+seti 1 2 4";
+
+    /// Runs the program with the first instruction setting `value`, literally and optimized
+    fn run_both(source: &str, value: u32) -> (Program<u32, 6>, Program<u32, 6>) {
+        let mut literal: Program<u32, 6> = source.parse().unwrap();
+        literal.instructions[0].a = value;
+        let mut optimized = literal.clone();
+        optimized.optimize();
+        literal.run();
+        optimized.run();
+        (literal, optimized)
+    }
+
+    #[test]
+    fn it_finds_idioms() {
+        let program: Program<u32, 6> = SUM_OF_DIVISORS.parse().unwrap();
+        let shortcuts = find_shortcuts(&Decompiled::new(&program));
+        assert_eq!(shortcuts.len(), 1);
+        assert_eq!(
+            (shortcuts[0].start, shortcuts[0].end, shortcuts[0].exit),
+            (1, 16, 16)
+        );
+
+        let program: Program<u32, 6> = DIVISION.parse().unwrap();
+        let shortcuts = find_shortcuts(&Decompiled::new(&program));
+        assert_eq!(
+            shortcuts,
+            vec![Shortcut {
+                idiom: Idiom::Division {
+                    quotient: 3,
+                    dividend: 5,
+                    divisor: 256,
+                    scratch: 1,
+                },
+                start: 1,
+                end: 10,
+                exit: 10,
+            }]
+        );
+    }
+
+    #[test]
+    fn it_computes_idioms_like_the_loops() {
+        for value in [0, 1, 12, 17, 36, 100] {
+            let (literal, optimized) = run_both(SUM_OF_DIVISORS, value);
+            assert_eq!(literal.registers, optimized.registers, "sum for {}", value);
+            assert!(optimized.instructions_executed() < literal.instructions_executed());
+        }
+        for value in [0, 255, 256, 1000, 65536] {
+            let (literal, optimized) = run_both(DIVISION, value);
+            assert_eq!(
+                literal.registers, optimized.registers,
+                "division of {}",
+                value
+            );
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use crate::optimizer::{find_shortcuts, Shortcut};
+use crate::{Decompiled, Instruction, Op, Tracer, Word};
+use common::{end_of_input, lines, Line, ParseError, ParseResult};
+use log::trace;
+use std::fmt;
+use std::str::FromStr;
+
+/// Instructions with `N` registers of type `R`, optionally with the instruction pointer bound
+/// to one of the registers by an `#ip` directive
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Program<R = u64, const N: usize = 6> {
+    pub instructions: Vec<Instruction<R>>,
+    pub ip_register: Option<usize>,
+    pub registers: [R; N],
+    /// The instruction pointer when it isn't bound to a register
+    ip: usize,
+    instructions_executed: u64,
+    /// Loops `run` and `run_to_line` compute natively, see `optimize`
+    shortcuts: Vec<Shortcut<R>>,
+}
+
+impl<R: Word, const N: usize> Program<R, N> {
+    pub fn new(instructions: Vec<Instruction<R>>, ip_register: Option<usize>) -> Self {
+        assert!(
+            ip_register.is_none_or(|r| r < N),
+            "Instruction pointer register {:?} out of range",
+            ip_register
+        );
+        Self {
+            instructions,
+            ip_register,
+            registers: [R::ZERO; N],
+            ip: 0,
+            instructions_executed: 0,
+            shortcuts: vec![],
+        }
+    }
+
+    /// Parses instructions written as numbers, like `9 2 1 2`, looking up the op for each number
+    ///
+    /// Takes the numbered lines of the program, which may be a section of a larger input.
+    pub fn parse_numeric<'a, L, F>(program_lines: L, mut op_for_number: F) -> ParseResult<Self>
+    where
+        L: IntoIterator<Item = Line<'a>>,
+        F: FnMut(usize) -> Option<Op>,
+    {
+        let mut instructions = vec![];
+        for line in program_lines {
+            let mut cursor = line.cursor();
+            cursor.skip_whitespace();
+            if cursor.rest().is_empty() {
+                continue;
+            }
+            let start = cursor;
+            let op = cursor
+                .number("a known op number")
+                .ok()
+                .and_then(&mut op_for_number)
+                .ok_or_else(|| start.error("a known op number"))?;
+            instructions.push(Instruction::read_operands(op, &mut cursor, N)?);
+        }
+        Ok(Self::new(instructions, None))
+    }
+
+    pub fn ip(&self) -> usize {
+        match self.ip_register {
+            Some(r) => self.registers[r].to_usize(),
+            None => self.ip,
+        }
+    }
+
+    pub fn set_ip(&mut self, ip: usize) {
+        match self.ip_register {
+            Some(r) => self.registers[r] = R::from_usize(ip),
+            None => self.ip = ip,
+        }
+    }
+
+    /// The instruction at the instruction pointer, `None` once the program has halted
+    pub fn current_instruction(&self) -> Option<&Instruction<R>> {
+        self.instructions.get(self.ip())
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.current_instruction().is_none()
+    }
+
+    pub fn instructions_executed(&self) -> u64 {
+        self.instructions_executed
+    }
+
+    /// Executes the instruction at the instruction pointer, returning false if halted instead
+    ///
+    /// The registers before and after the instruction are logged at the trace level.
+    pub fn step(&mut self) -> bool {
+        let instruction = match self.current_instruction() {
+            Some(&instruction) => instruction,
+            None => return false,
+        };
+        trace!("ip={} {:?} {:?}", self.ip(), instruction, self.registers);
+        instruction.execute(&mut self.registers);
+        self.instructions_executed += 1;
+        let ip = self.ip();
+        trace!("ip={} {:?} {:?}", ip, instruction, self.registers);
+        self.set_ip(ip.saturating_add(1));
+        true
+    }
+
+    /// Like `run` but executes instructions one by one, recording each in the tracer, and
+    /// gives up after `max_steps`, returning whether the program halted
+    pub fn run_traced(&mut self, tracer: &mut Tracer<R, N>, max_steps: u64) -> bool {
+        for _ in 0..max_steps {
+            let line = self.ip();
+            if !self.step() {
+                return true;
+            }
+            tracer.record(line, &self.registers);
+        }
+        self.is_halted()
+    }
+
+    /// Makes `run` and `run_to_line` compute known loop idioms natively, like summing the
+    /// divisors of a number, instead of executing them instruction by instruction
+    ///
+    /// Stepping still executes single instructions. Instructions skipped by a shortcut don't
+    /// count as executed.
+    pub fn optimize(&mut self) {
+        self.shortcuts = find_shortcuts(&Decompiled::new(self));
+    }
+
+    pub fn shortcuts(&self) -> &[Shortcut<R>] {
+        &self.shortcuts
+    }
+
+    /// Computes the loop starting at the instruction pointer natively if it is a known idiom
+    /// that doesn't contain `stop_at`, returning whether it did
+    fn take_shortcut(&mut self, stop_at: Option<usize>) -> bool {
+        let ip = self.ip();
+        let shortcut = match self.shortcuts.iter().find(|s| s.start == ip) {
+            Some(&shortcut) => shortcut,
+            None => return false,
+        };
+        if stop_at.is_some_and(|line| shortcut.start < line && line < shortcut.end) {
+            return false;
+        }
+        shortcut.apply(&mut self.registers);
+        self.set_ip(shortcut.exit);
+        true
+    }
+
+    /// Runs until the instruction pointer points outside of the program
+    pub fn run(&mut self) {
+        while self.take_shortcut(None) || self.step() {}
+    }
+
+    /// Runs at least one instruction and stops before executing `line`, returning false if the
+    /// program halted instead
+    pub fn run_to_line(&mut self, line: usize) -> bool {
+        while self.take_shortcut(Some(line)) || self.step() {
+            if self.ip() == line {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Parses an optional `#ip <register>` directive followed by one instruction per line
+impl<R: Word, const N: usize> FromStr for Program<R, N> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ip_register = None;
+        let mut instructions = vec![];
+        for line in lines(s) {
+            let mut cursor = line.cursor();
+            cursor.skip_whitespace();
+            if cursor.rest().is_empty() {
+                continue;
+            }
+            if !cursor.rest().starts_with('#') {
+                instructions.push(Instruction::from_line(&line, N)?);
+                continue;
+            }
+            if ip_register.is_some() || !instructions.is_empty() {
+                return Err(cursor.error("an instruction, #ip must be the first line"));
+            }
+            cursor.literal("#ip")?;
+            cursor.skip_whitespace();
+            let expected = format!("a register below {}", N);
+            let start = cursor;
+            let register = cursor.number(&expected)?;
+            if register >= N {
+                return Err(start.error(expected));
+            }
+            cursor.end()?;
+            ip_register = Some(register);
+        }
+        if instructions.is_empty() {
+            return Err(end_of_input(s, "an instruction"));
+        }
+        Ok(Self::new(instructions, ip_register))
+    }
+}
+
+impl<R: Word, const N: usize> fmt::Display for Program<R, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(r) = self.ip_register {
+            writeln!(f, "#ip {}", r)?;
+        }
+        for instruction in self.instructions.iter() {
+            writeln!(f, "{}", instruction)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_INPUT: &str =
+        "#ip 0\nseti 5 0 1\nseti 6 0 2\naddi 0 1 0\naddr 1 2 3\nsetr 1 0 0\nseti 8 0 4\nseti 9 0 5";
+
+    #[test]
+    fn it_runs_programs_with_a_bound_ip() {
+        let mut program: Program<u32, 6> = SAMPLE_INPUT.parse().unwrap();
+        assert_eq!(program.ip_register, Some(0));
+        program.run();
+        assert_eq!(program.registers, [7, 5, 6, 0, 0, 9]);
+        assert_eq!(program.instructions_executed(), 5);
+        assert!(program.is_halted());
+        assert!(!program.step());
+    }
+
+    #[test]
+    fn it_runs_to_lines() {
+        let mut program: Program<u32, 6> = SAMPLE_INPUT.parse().unwrap();
+        assert!(program.run_to_line(2));
+        assert_eq!(program.registers, [2, 5, 6, 0, 0, 0]);
+        assert!(program.run_to_line(6));
+        assert_eq!(
+            program.current_instruction().unwrap().to_string(),
+            "seti 9 0 5"
+        );
+        assert!(!program.run_to_line(2));
+    }
+
+    #[test]
+    fn it_parses_numeric_programs() {
+        let ops = [Op::Seti, Op::Addi, Op::Mulr];
+        let mut program: Program<u32, 4> =
+            Program::parse_numeric(lines("0 3 0 0\n1 0 4 1\n2 0 1 2\n"), |n| {
+                ops.get(n).cloned()
+            })
+            .unwrap();
+        assert_eq!(program.ip_register, None);
+        program.run();
+        assert_eq!(program.registers, [3, 7, 21, 0]);
+        let error =
+            Program::<u32, 4>::parse_numeric(lines("0 3 0 0\n3 0 0 0"), |n| ops.get(n).cloned());
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "line 2, column 1: expected a known op number, found \"3\""
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_programs() {
+        assert_eq!(
+            "#ip 6\nseti 5 0 1".parse::<Program<u32, 6>>().unwrap_err(),
+            ParseError::new(1, 5, "a register below 6", "6")
+        );
+        assert_eq!(
+            "seti 5 0 1\n#ip 0".parse::<Program<u32, 6>>().unwrap_err(),
+            ParseError::new(2, 1, "an instruction, #ip must be the first line", "#")
+        );
+        let error = "#ip 0\nseti 5 0 1\nsett 1 2 3"
+            .parse::<Program<u32, 6>>()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected an op name, found \"sett\""
+        );
+        let error = "#ip 0\n  seti 5 x 1"
+            .parse::<Program<u32, 6>>()
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 10));
+        assert_eq!(
+            "#ip 1\naddr 9 0 0".parse::<Program<u32, 6>>().unwrap_err(),
+            ParseError::new(2, 6, "a register below 6", "9")
+        );
+        let error = Program::<u32, 4>::parse_numeric(lines("0 1 2 4"), |_| Some(Op::Addr));
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "line 1, column 7: expected a register below 4, found \"4\""
+        );
+        let error = "seti 5 0".parse::<Program<u32, 6>>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 9: expected an operand, found nothing"
+        );
+        assert_eq!(
+            "#ip 0\n".parse::<Program<u32, 6>>().unwrap_err(),
+            ParseError::new(2, 1, "an instruction", "")
+        );
+    }
+
+    #[test]
+    fn it_formats_programs() {
+        let program: Program = SAMPLE_INPUT.parse().unwrap();
+        assert_eq!(program.to_string(), format!("{}\n", SAMPLE_INPUT));
+    }
+}
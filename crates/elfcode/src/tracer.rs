@@ -0,0 +1,172 @@
+use crate::{Program, Word};
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+/// The registers after executing the instruction at `line`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEntry<R, const N: usize> {
+    pub step: u64,
+    pub line: usize,
+    pub registers: [R; N],
+}
+
+/// Records how often each line runs and, optionally, the most recent register values
+#[derive(Debug, Clone, Default)]
+pub struct Tracer<R, const N: usize> {
+    counts: Vec<u64>,
+    steps: u64,
+    history_limit: usize,
+    history: VecDeque<TraceEntry<R, N>>,
+}
+
+impl<R: Word, const N: usize> Tracer<R, N> {
+    /// A tracer that only counts executions
+    pub fn new() -> Self {
+        Self {
+            counts: vec![],
+            steps: 0,
+            history_limit: 0,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// A tracer that also keeps the registers after each of the last `limit` steps
+    pub fn with_history(limit: usize) -> Self {
+        Self {
+            history_limit: limit,
+            ..Self::new()
+        }
+    }
+
+    pub fn record(&mut self, line: usize, registers: &[R; N]) {
+        if line >= self.counts.len() {
+            self.counts.resize(line + 1, 0);
+        }
+        self.counts[line] += 1;
+        self.steps += 1;
+        if self.history_limit > 0 {
+            if self.history.len() == self.history_limit {
+                self.history.pop_front();
+            }
+            self.history.push_back(TraceEntry {
+                step: self.steps,
+                line,
+                registers: *registers,
+            });
+        }
+    }
+
+    /// Number of times each line was executed
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn history(&self) -> impl Iterator<Item = &TraceEntry<R, N>> {
+        self.history.iter()
+    }
+
+    /// Executed lines, most executed first
+    pub fn hot_spots(&self) -> Vec<(usize, u64)> {
+        let mut hot_spots: Vec<_> = self
+            .counts
+            .iter()
+            .cloned()
+            .enumerate()
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        hot_spots.sort_by_key(|&(line, count)| (std::cmp::Reverse(count), line));
+        hot_spots
+    }
+
+    /// Writes the recorded history as CSV with a `step,line,r0,r1,...` header
+    pub fn write_csv<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let registers: Vec<_> = (0..N).map(|r| format!("r{}", r)).collect();
+        writeln!(output, "step,line,{}", registers.join(","))?;
+        for entry in self.history.iter() {
+            let values: Vec<_> = entry.registers.iter().map(|v| v.to_string()).collect();
+            writeln!(output, "{},{},{}", entry.step, entry.line, values.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the `limit` most executed lines with their share of the steps
+    pub fn write_report<W: Write>(
+        &self,
+        program: &Program<R, N>,
+        limit: usize,
+        output: &mut W,
+    ) -> io::Result<()> {
+        writeln!(
+            output,
+            "{:>6} {:>14} {:>7}  instruction",
+            "line", "count", "share"
+        )?;
+        for (line, count) in self.hot_spots().into_iter().take(limit) {
+            let share = 100.0 * count as f64 / self.steps as f64;
+            let instruction = program
+                .instructions
+                .get(line)
+                .map(|i| i.to_string())
+                .unwrap_or_default();
+            writeln!(
+                output,
+                "{:>6} {:>14} {:>6.2}%  {}",
+                line, count, share, instruction
+            )?;
+        }
+        writeln!(output, "{} instructions executed", self.steps)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_INPUT: &str =
+        "#ip 0\nseti 5 0 1\nseti 6 0 2\naddi 0 1 0\naddr 1 2 3\nsetr 1 0 0\nseti 8 0 4\nseti 9 0 5";
+
+    // Counts r1 up to 3
+    const LOOP_INPUT: &str =
+        "#ip 0\nseti 0 0 1\naddi 1 1 1\ngtri 1 2 2\naddr 0 2 0\nseti 0 0 0\nseti 9 0 0";
+
+    #[test]
+    fn it_records_traces() {
+        let mut program: Program<u32, 6> = SAMPLE_INPUT.parse().unwrap();
+        let mut tracer = Tracer::with_history(2);
+        assert!(program.run_traced(&mut tracer, 100));
+        assert_eq!(tracer.counts(), &[1, 1, 1, 0, 1, 0, 1]);
+        assert_eq!(tracer.steps(), 5);
+        let mut csv = vec![];
+        tracer.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "step,line,r0,r1,r2,r3,r4,r5\n4,4,6,5,6,0,0,0\n5,6,7,5,6,0,0,9\n"
+        );
+    }
+
+    #[test]
+    fn it_reports_hot_spots() {
+        let mut program: Program<u32, 6> = LOOP_INPUT.parse().unwrap();
+        let mut tracer = Tracer::new();
+        assert!(!program.run_traced(&mut tracer, 10));
+        assert_eq!(
+            tracer.hot_spots(),
+            vec![(1, 3), (2, 2), (3, 2), (4, 2), (0, 1)]
+        );
+        assert_eq!(tracer.history().count(), 0);
+        let mut report = vec![];
+        tracer.write_report(&program, 2, &mut report).unwrap();
+        assert_eq!(
+            String::from_utf8(report).unwrap(),
+            "  line          count   share  instruction
+     1              3  30.00%  addi 1 1 1
+     2              2  20.00%  gtri 1 2 2
+10 instructions executed
+"
+        );
+    }
+}
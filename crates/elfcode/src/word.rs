@@ -0,0 +1,82 @@
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// An unsigned integer type that can be used for registers, arithmetic wraps around on overflow
+pub trait Word: Copy + Default + Eq + Ord + Hash + Debug + Display + FromStr {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn wrapping_add(self, other: Self) -> Self;
+
+    fn wrapping_mul(self, other: Self) -> Self;
+
+    fn bitand(self, other: Self) -> Self;
+
+    fn bitor(self, other: Self) -> Self;
+
+    /// Converts a value, wrapping it around when it doesn't fit
+    fn from_usize(value: usize) -> Self;
+
+    /// Converts the value, saturating at `usize::MAX` when it doesn't fit
+    fn to_usize(self) -> usize;
+
+    fn from_bool(value: bool) -> Self {
+        if value {
+            Self::ONE
+        } else {
+            Self::ZERO
+        }
+    }
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn wrapping_add(self, other: Self) -> Self {
+                    <$t>::wrapping_add(self, other)
+                }
+
+                fn wrapping_mul(self, other: Self) -> Self {
+                    <$t>::wrapping_mul(self, other)
+                }
+
+                fn bitand(self, other: Self) -> Self {
+                    self & other
+                }
+
+                fn bitor(self, other: Self) -> Self {
+                    self | other
+                }
+
+                fn from_usize(value: usize) -> Self {
+                    value as $t
+                }
+
+                fn to_usize(self) -> usize {
+                    usize::try_from(self).unwrap_or(usize::MAX)
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_wraps_around_on_overflow() {
+        assert_eq!(Word::wrapping_add(255u8, 2), 1);
+        assert_eq!(Word::wrapping_mul(1u32 << 31, 2), 0);
+        assert_eq!(u8::from_usize(258), 2);
+        assert_eq!(u64::from_bool(true), 1);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::Point;
+
+/// An inclusive box of points, a rectangle in two dimensions and a cuboid in three
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<const N: usize> {
+    pub min: Point<N>,
+    pub max: Point<N>,
+}
+
+impl<const N: usize> BoundingBox<N> {
+    pub fn new(min: Point<N>, max: Point<N>) -> Self {
+        Self { min, max }
+    }
+
+    /// The smallest box containing every point, `None` when there are no points
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Point<N>>,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |bounds, p| bounds.including(&p)))
+    }
+
+    /// The smallest box containing both this box and the point
+    pub fn including(&self, point: &Point<N>) -> Self {
+        Self {
+            min: self.min.zip_with(point, i64::min),
+            max: self.max.zip_with(point, i64::max),
+        }
+    }
+
+    pub fn contains(&self, point: &Point<N>) -> bool {
+        (0..N).all(|i| (self.min[i]..=self.max[i]).contains(&point[i]))
+    }
+
+    /// Number of points along each axis
+    pub fn size(&self) -> Point<N> {
+        self.max - self.min + Point::splat(1)
+    }
+
+    /// Manhattan distance from the point to the closest point in the box, zero when inside
+    pub fn manhattan_distance_to(&self, point: &Point<N>) -> u64 {
+        (0..N)
+            .map(|i| {
+                if point[i] < self.min[i] {
+                    self.min[i].abs_diff(point[i])
+                } else if point[i] > self.max[i] {
+                    point[i].abs_diff(self.max[i])
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    /// Every point within the box in reading order
+    pub fn points(&self) -> Points<N> {
+        Points {
+            bounds: *self,
+            next: if (0..N).all(|i| self.min[i] <= self.max[i]) {
+                Some(self.min)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl BoundingBox<2> {
+    pub fn width(&self) -> u64 {
+        self.size().x() as u64
+    }
+
+    pub fn height(&self) -> u64 {
+        self.size().y() as u64
+    }
+}
+
+/// Iterator over the points of a `BoundingBox`, see `BoundingBox::points`
+#[derive(Debug, Clone)]
+pub struct Points<const N: usize> {
+    bounds: BoundingBox<N>,
+    next: Option<Point<N>>,
+}
+
+impl<const N: usize> Iterator for Points<N> {
+    type Item = Point<N>;
+
+    fn next(&mut self) -> Option<Point<N>> {
+        let current = self.next?;
+        let mut next = current;
+        // Advance like an odometer with the first axis turning fastest
+        self.next = None;
+        for i in 0..N {
+            if next[i] < self.bounds.max[i] {
+                next[i] += 1;
+                self.next = Some(next);
+                break;
+            }
+            next[i] = self.bounds.min[i];
+        }
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_finds_bounds_of_points() {
+        assert_eq!(BoundingBox::<2>::from_points(vec![]), None);
+        let bounds = BoundingBox::from_points(vec![
+            Point::new([500, 0]),
+            Point::new([495, 13]),
+            Point::new([506, -2]),
+        ])
+        .unwrap();
+        assert_eq!(
+            bounds,
+            BoundingBox::new(Point::new([495, -2]), Point::new([506, 13]))
+        );
+        assert_eq!((bounds.width(), bounds.height()), (12, 16));
+        assert!(bounds.contains(&Point::new([495, 13])));
+        assert!(!bounds.contains(&Point::new([494, 13])));
+    }
+
+    #[test]
+    fn it_measures_distance_to_a_box() {
+        let bounds = BoundingBox::new(Point::new([0, 0, 0]), Point::new([2, 2, 2]));
+        assert_eq!(bounds.manhattan_distance_to(&Point::new([1, 1, 1])), 0);
+        assert_eq!(bounds.manhattan_distance_to(&Point::new([1, 5, 1])), 3);
+        assert_eq!(bounds.manhattan_distance_to(&Point::new([-1, 5, 3])), 5);
+    }
+
+    #[test]
+    fn it_iterates_points_in_reading_order() {
+        let bounds = BoundingBox::new(Point::new([1, 1]), Point::new([2, 3]));
+        let points: Vec<_> = bounds.points().collect();
+        assert_eq!(points.len(), 6);
+        assert_eq!(
+            &points[..3],
+            &[Point::new([1, 1]), Point::new([2, 1]), Point::new([1, 2])]
+        );
+        let mut sorted = points.clone();
+        sorted.sort();
+        assert_eq!(points, sorted);
+        let empty = BoundingBox::new(Point::new([1, 1]), Point::new([0, 3]));
+        assert_eq!(empty.points().count(), 0);
+    }
+}
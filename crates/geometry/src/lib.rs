@@ -0,0 +1,400 @@
+//! Points with any number of integer coordinates, with `y` growing downwards in two dimensions
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+mod bounding_box;
+
+pub use crate::bounding_box::{BoundingBox, Points};
+
+pub type Point2 = Point<2>;
+pub type Point3 = Point<3>;
+pub type Point4 = Point<4>;
+
+/// A point with `N` integer coordinates
+///
+/// Points are ordered in reading order, comparing the last coordinate first, so in two
+/// dimensions they are sorted by `y` and then by `x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point<const N: usize>(pub [i64; N]);
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Self = Point([0; N]);
+
+    pub const fn new(coordinates: [i64; N]) -> Self {
+        Point(coordinates)
+    }
+
+    /// A point with every coordinate set to the value
+    pub const fn splat(value: i64) -> Self {
+        Point([value; N])
+    }
+
+    pub fn coordinates(&self) -> [i64; N] {
+        self.0
+    }
+
+    /// Sum of the absolute differences of the coordinates
+    pub fn manhattan_distance(&self, other: &Self) -> u64 {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| a.abs_diff(*b))
+            .sum()
+    }
+
+    /// Largest absolute difference of the coordinates
+    pub fn chebyshev_distance(&self, other: &Self) -> u64 {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Compares the last coordinate first, so `y` before `x` in two dimensions
+    pub fn reading_order(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+
+    /// The `2 * N` points one step away along a single axis, in reading order
+    pub fn orthogonal_neighbors(&self) -> Vec<Self> {
+        self.all_neighbors()
+            .into_iter()
+            .filter(|p| self.manhattan_distance(p) == 1)
+            .collect()
+    }
+
+    /// The `3^N - 1` points one step away along any number of axes, in reading order
+    pub fn all_neighbors(&self) -> Vec<Self> {
+        BoundingBox::new(*self - Self::splat(1), *self + Self::splat(1))
+            .points()
+            .filter(|p| p != self)
+            .collect()
+    }
+
+    /// Applies the function to every coordinate
+    pub fn map<F>(&self, f: F) -> Self
+    where
+        F: FnMut(i64) -> i64,
+    {
+        Point(self.0.map(f))
+    }
+
+    /// Applies the function to every pair of coordinates
+    pub fn zip_with<F>(&self, other: &Self, mut f: F) -> Self
+    where
+        F: FnMut(i64, i64) -> i64,
+    {
+        let mut result = *self;
+        for (a, b) in result.0.iter_mut().zip(other.0.iter()) {
+            *a = f(*a, *b);
+        }
+        result
+    }
+}
+
+impl Point<2> {
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+}
+
+impl Point<3> {
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> i64 {
+        self.0[2]
+    }
+}
+
+impl Point<4> {
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> i64 {
+        self.0[2]
+    }
+
+    pub fn w(&self) -> i64 {
+        self.0[3]
+    }
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const N: usize> Ord for Point<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.reading_order(other)
+    }
+}
+
+impl<const N: usize> PartialOrd for Point<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip_with(&other, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip_with(&other, |a, b| a - b)
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|a| -a)
+    }
+}
+
+impl<const N: usize> Mul<i64> for Point<N> {
+    type Output = Self;
+
+    fn mul(self, factor: i64) -> Self {
+        self.map(|a| a * factor)
+    }
+}
+
+impl<const N: usize> AddAssign for Point<N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const N: usize> SubAssign for Point<N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for Point<N> {
+    fn from(coordinates: [i64; N]) -> Self {
+        Point(coordinates)
+    }
+}
+
+impl From<(i64, i64)> for Point<2> {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point([x, y])
+    }
+}
+
+impl From<(usize, usize)> for Point<2> {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point([x as i64, y as i64])
+    }
+}
+
+impl From<(i64, i64, i64)> for Point<3> {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point([x, y, z])
+    }
+}
+
+/// Converts to an unsigned `(x, y)` position, failing when either coordinate is negative
+impl TryFrom<Point<2>> for (usize, usize) {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(point: Point<2>) -> Result<Self, Self::Error> {
+        Ok((usize::try_from(point.x())?, usize::try_from(point.y())?))
+    }
+}
+
+/// Formats the coordinates separated by commas, like `3,-4`
+impl<const N: usize> fmt::Display for Point<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, coordinate) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", coordinate)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParsePointError {
+    InvalidCoordinate(ParseIntError),
+    WrongDimension { expected: usize, found: usize },
+}
+
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePointError::InvalidCoordinate(e) => write!(f, "Invalid coordinate: {}", e),
+            ParsePointError::WrongDimension { expected, found } => {
+                write!(f, "Expected {} coordinates but found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl Error for ParsePointError {}
+
+/// Parses comma separated coordinates, ignoring whitespace around each of them
+impl<const N: usize> FromStr for Point<N> {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coordinates = s
+            .split(',')
+            .map(|c| c.trim().parse())
+            .collect::<Result<Vec<i64>, _>>()
+            .map_err(ParsePointError::InvalidCoordinate)?;
+        let found = coordinates.len();
+        <[i64; N]>::try_from(coordinates)
+            .map(Point)
+            .map_err(|_| ParsePointError::WrongDimension { expected: N, found })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::convert::TryInto;
+
+    #[test]
+    fn it_calculates_distances() {
+        let a = Point::new([0, 0]);
+        let b = Point::new([1, 1]);
+        let c = Point::new([5, -10]);
+        assert_eq!(a.manhattan_distance(&b), 2);
+        assert_eq!(a.manhattan_distance(&c), 15);
+        assert_eq!(c.manhattan_distance(&b), 15);
+        assert_eq!(a.chebyshev_distance(&c), 10);
+        assert_eq!(b.chebyshev_distance(&b), 0);
+        let d = Point::new([1, -2, 3, -4]);
+        assert_eq!(d.manhattan_distance(&Point::ORIGIN), 10);
+        assert_eq!(d.chebyshev_distance(&Point::ORIGIN), 4);
+    }
+
+    #[test]
+    fn it_does_arithmetic() {
+        let a = Point::new([1, 2, 3]);
+        let b = Point::new([-1, 5, 0]);
+        assert_eq!(a + b, Point::new([0, 7, 3]));
+        assert_eq!(a - b, Point::new([2, -3, 3]));
+        assert_eq!(-a, Point::new([-1, -2, -3]));
+        assert_eq!(a * 3, Point::new([3, 6, 9]));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        c[1] = 4;
+        assert_eq!(c.y(), 4);
+    }
+
+    #[test]
+    fn it_orders_points_in_reading_order() {
+        let mut points = vec![
+            Point::new([2, 1]),
+            Point::new([1, 2]),
+            Point::new([0, 1]),
+            Point::new([3, 0]),
+        ];
+        points.sort();
+        assert_eq!(
+            points,
+            vec![
+                Point::new([3, 0]),
+                Point::new([0, 1]),
+                Point::new([2, 1]),
+                Point::new([1, 2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_finds_neighbors_in_reading_order() {
+        let p = Point::new([0, 0]);
+        assert_eq!(
+            p.orthogonal_neighbors(),
+            vec![
+                Point::new([0, -1]),
+                Point::new([-1, 0]),
+                Point::new([1, 0]),
+                Point::new([0, 1]),
+            ]
+        );
+        let neighbors = p.all_neighbors();
+        assert_eq!(neighbors.len(), 8);
+        assert_eq!(neighbors[0], Point::new([-1, -1]));
+        assert_eq!(neighbors[7], Point::new([1, 1]));
+        assert_eq!(Point::new([0, 0, 0, 0]).all_neighbors().len(), 80);
+    }
+
+    #[test]
+    fn it_parses_and_formats_points() {
+        let p: Point3 = "1, -2,3".parse().unwrap();
+        assert_eq!(p, Point::new([1, -2, 3]));
+        assert_eq!(p.to_string(), "1,-2,3");
+        assert_eq!(
+            "1,2".parse::<Point3>(),
+            Err(ParsePointError::WrongDimension {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert!("1,x".parse::<Point2>().is_err());
+    }
+
+    #[test]
+    fn it_converts_to_and_from_positions() {
+        let p = Point2::from((3usize, 4usize));
+        assert_eq!(p.try_into(), Ok((3usize, 4usize)));
+        let negative: Result<(usize, usize), _> = Point::new([-1, 4]).try_into();
+        assert!(negative.is_err());
+    }
+}
@@ -0,0 +1,419 @@
+//! Two dimensional grids of cells addressed by `(x, y)` positions with `y` growing downwards
+
+use std::convert::Infallible;
+use std::fmt;
+use std::iter::{self, FromIterator};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+mod storage;
+
+pub use crate::storage::{Dense, Sparse, Storage};
+
+/// An `(x, y)` position in a grid
+pub type Position = (usize, usize);
+
+/// A grid that only stores the cells that have been inserted
+pub type SparseGrid<T> = Grid<T, Sparse<T>>;
+
+/// Offsets of the 4 orthogonal neighbors in reading order
+const NEIGHBOR_4_OFFSETS: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the 8 orthogonal and diagonal neighbors in reading order
+const NEIGHBOR_8_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// An inclusive rectangle of positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Bounds {
+    pub fn new(min: Position, max: Position) -> Self {
+        Self { min, max }
+    }
+
+    pub fn width(&self) -> usize {
+        self.max.0 - self.min.0 + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.1 - self.min.1 + 1
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        (self.min.0..=self.max.0).contains(&x) && (self.min.1..=self.max.1).contains(&y)
+    }
+
+    pub fn is_on_edge(&self, (x, y): Position) -> bool {
+        self.contains((x, y))
+            && (x == self.min.0 || x == self.max.0 || y == self.min.1 || y == self.max.1)
+    }
+
+    /// The smallest bounds containing both these bounds and the position
+    pub fn including(&self, (x, y): Position) -> Self {
+        Self {
+            min: (self.min.0.min(x), self.min.1.min(y)),
+            max: (self.max.0.max(x), self.max.1.max(y)),
+        }
+    }
+
+    /// Every position within the bounds in reading order
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let (min_x, max_x) = (self.min.0, self.max.0);
+        (self.min.1..=self.max.1).flat_map(move |y| (min_x..=max_x).map(move |x| (x, y)))
+    }
+}
+
+/// Positions next to the given one in reading order, skipping any that would be negative
+fn offset_positions(
+    (x, y): Position,
+    offsets: &'static [(isize, isize)],
+) -> impl Iterator<Item = Position> {
+    offsets.iter().filter_map(move |&(dx, dy)| {
+        let x = (x as isize).checked_add(dx)?;
+        let y = (y as isize).checked_add(dy)?;
+        if x < 0 || y < 0 {
+            None
+        } else {
+            Some((x as usize, y as usize))
+        }
+    })
+}
+
+/// The 4 orthogonally adjacent positions in reading order
+pub fn neighbors4(position: Position) -> impl Iterator<Item = Position> {
+    offset_positions(position, &NEIGHBOR_4_OFFSETS)
+}
+
+/// The 8 orthogonally and diagonally adjacent positions in reading order
+pub fn neighbors8(position: Position) -> impl Iterator<Item = Position> {
+    offset_positions(position, &NEIGHBOR_8_OFFSETS)
+}
+
+/// A grid of cells backed by either `Dense` or `Sparse` storage
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T, S = Dense<T>> {
+    storage: S,
+    cell: PhantomData<T>,
+}
+
+impl<T, S: Storage<T>> Grid<T, S> {
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.storage.get(position)
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.storage.get_mut(position)
+    }
+
+    /// Stores the value at the position, returning the value that was there before
+    pub fn insert(&mut self, position: Position, value: T) -> Option<T> {
+        self.storage.insert(position, value)
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.get(position).is_some()
+    }
+
+    /// The smallest bounds containing every cell, `None` when there are no cells
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.storage.bounds()
+    }
+
+    /// Every cell with its position, dense grids are visited in reading order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.storage.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        self.storage.iter_mut()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.iter().map(|(_, cell)| cell)
+    }
+
+    /// The cells orthogonally adjacent to the position in reading order
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        neighbors4(position).filter_map(move |p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// The cells orthogonally and diagonally adjacent to the position in reading order
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        neighbors8(position).filter_map(move |p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// Renders every position within the grid's bounds as one line per row
+    pub fn render<F>(&self, to_char: F) -> String
+    where
+        F: FnMut(Position, Option<&T>) -> char,
+    {
+        match self.bounds() {
+            Some(bounds) => self.render_area(bounds, to_char),
+            None => String::new(),
+        }
+    }
+
+    /// Renders every position within the bounds as one line per row, the bounds may extend
+    /// past the grid's cells
+    pub fn render_area<F>(&self, bounds: Bounds, mut to_char: F) -> String
+    where
+        F: FnMut(Position, Option<&T>) -> char,
+    {
+        let mut s = String::with_capacity((bounds.width() + 1) * bounds.height());
+        for y in bounds.min.1..=bounds.max.1 {
+            for x in bounds.min.0..=bounds.max.0 {
+                s.push(to_char((x, y), self.get((x, y))));
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_storage(Dense::from_cells(
+            width,
+            height,
+            vec![value; width * height],
+        ))
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, f: F) -> Self
+    where
+        F: FnMut(Position) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(f)
+            .collect();
+        Self::from_storage(Dense::from_cells(width, height, cells))
+    }
+
+    /// Builds a grid from equal length rows
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "Rows must all be the same length"
+        );
+        let cells = rows.into_iter().flatten().collect();
+        Self::from_storage(Dense::from_cells(width, height, cells))
+    }
+
+    /// Parses a map with a character per cell and a line per row
+    ///
+    /// Rows shorter than the longest one are padded as if they ended in spaces.
+    pub fn from_char_map<F>(input: &str, mut f: F) -> Self
+    where
+        F: FnMut(Position, char) -> T,
+    {
+        Self::try_from_char_map(input, |position, c| Ok::<_, Infallible>(f(position, c)))
+            .unwrap_or_else(|never| match never {})
+    }
+
+    /// Parses a map like `from_char_map`, stopping at the first cell `f` fails on
+    pub fn try_from_char_map<F, E>(input: &str, mut f: F) -> Result<Self, E>
+    where
+        F: FnMut(Position, char) -> Result<T, E>,
+    {
+        let lines: Vec<_> = input.lines().collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let rows = lines
+            .iter()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .chain(iter::repeat(' '))
+                    .take(width)
+                    .enumerate()
+                    .map(|(x, c)| f((x, y), c))
+                    .collect()
+            })
+            .collect::<Result<_, E>>()?;
+        Ok(Self::from_rows(rows))
+    }
+
+    pub fn width(&self) -> usize {
+        self.storage.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.storage.height()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.storage.rows()
+    }
+
+    /// Changes the size of the grid, keeping the cells that are still within it and filling
+    /// new ones with the value
+    pub fn resize(&mut self, width: usize, height: usize, value: T)
+    where
+        T: Clone,
+    {
+        self.storage.resize(width, height, value);
+    }
+}
+
+impl<T> Default for Grid<T, Sparse<T>> {
+    fn default() -> Self {
+        Self::from_storage(Sparse::default())
+    }
+}
+
+impl<T, S> Grid<T, S> {
+    fn from_storage(storage: S) -> Self {
+        Self {
+            storage,
+            cell: PhantomData,
+        }
+    }
+}
+
+impl<T> FromIterator<(Position, T)> for Grid<T, Sparse<T>> {
+    fn from_iter<I: IntoIterator<Item = (Position, T)>>(iter: I) -> Self {
+        let mut grid = Self::default();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Position, T)> for Grid<T, Sparse<T>> {
+    fn extend<I: IntoIterator<Item = (Position, T)>>(&mut self, iter: I) {
+        for (position, value) in iter {
+            self.insert(position, value);
+        }
+    }
+}
+
+impl<T, S: Storage<T>> Index<Position> for Grid<T, S> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("No cell at {:?}", position))
+    }
+}
+
+impl<T, S: Storage<T>> IndexMut<Position> for Grid<T, S> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("No cell at {:?}", position))
+    }
+}
+
+/// Displays each cell within the bounds, positions without a cell are shown as spaces
+impl<T: fmt::Display, S: Storage<T>> fmt::Display for Grid<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        for y in bounds.min.1..=bounds.max.1 {
+            for x in bounds.min.0..=bounds.max.0 {
+                match self.get((x, y)) {
+                    Some(cell) => write!(f, "{}", cell)?,
+                    None => write!(f, " ")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAP: &str = "#.#\n.#.\n";
+
+    fn parse_map(input: &str) -> Grid<bool> {
+        Grid::from_char_map(input, |_, c| c == '#')
+    }
+
+    #[test]
+    fn it_parses_char_maps() {
+        let grid = parse_map(MAP);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[(0, 0)]);
+        assert!(!grid[(1, 0)]);
+        assert!(grid[(1, 1)]);
+        assert_eq!(grid.get((3, 0)), None);
+
+        let grid = Grid::from_char_map("/-\\\n|\n", |_, c| c);
+        assert_eq!(grid.render(|_, c| *c.unwrap()), "/-\\\n|  \n");
+
+        let error = Grid::try_from_char_map(MAP, |position, c| match c {
+            '#' => Ok(true),
+            _ => Err(position),
+        });
+        assert_eq!(error.unwrap_err(), (1, 0));
+    }
+
+    #[test]
+    fn it_finds_neighbors_within_bounds() {
+        let grid = Grid::from_fn(3, 3, |(x, y)| x + y * 3);
+        let neighbors: Vec<_> = grid.neighbors4((1, 1)).map(|(_, &v)| v).collect();
+        assert_eq!(neighbors, vec![1, 3, 5, 7]);
+        let neighbors: Vec<_> = grid.neighbors4((0, 0)).map(|(p, _)| p).collect();
+        assert_eq!(neighbors, vec![(1, 0), (0, 1)]);
+        let neighbors: Vec<_> = grid.neighbors8((1, 1)).map(|(_, &v)| v).collect();
+        assert_eq!(neighbors, vec![0, 1, 2, 3, 5, 6, 7, 8]);
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn it_renders_grids() {
+        let grid = parse_map(MAP);
+        let rendered = grid.render(|_, cell| if *cell.unwrap() { '#' } else { '.' });
+        assert_eq!(rendered, MAP);
+
+        let mut sparse = SparseGrid::default();
+        sparse.insert((2, 1), '#');
+        sparse.insert((4, 2), '~');
+        assert_eq!(format!("{}", sparse), "#  \n  ~\n");
+        let area = sparse.render_area(Bounds::new((1, 1), (5, 2)), |_, c| *c.unwrap_or(&'.'));
+        assert_eq!(area, ".#...\n...~.\n");
+    }
+
+    #[test]
+    fn it_resizes_dense_grids() {
+        let mut grid = parse_map(MAP);
+        grid.resize(4, 3, true);
+        assert_eq!(grid.bounds(), Some(Bounds::new((0, 0), (3, 2))));
+        assert!(grid[(0, 0)]);
+        assert!(!grid[(2, 1)]);
+        assert!(grid[(3, 0)]);
+        assert_eq!(grid.rows().count(), 3);
+    }
+
+    #[test]
+    fn it_handles_bounds() {
+        let bounds = Bounds::new((1, 1), (3, 2));
+        assert_eq!((bounds.width(), bounds.height()), (3, 2));
+        assert!(bounds.contains((3, 2)));
+        assert!(!bounds.contains((0, 2)));
+        assert!(bounds.is_on_edge((1, 2)));
+        assert_eq!(bounds.including((0, 5)), Bounds::new((0, 1), (3, 5)));
+        let positions: Vec<_> = Bounds::new((0, 0), (1, 1)).positions().collect();
+        assert_eq!(positions, vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{Bounds, Position};
+use std::collections::HashMap;
+use std::mem;
+
+/// Storage for the cells of a `Grid`
+pub trait Storage<T> {
+    fn get(&self, position: Position) -> Option<&T>;
+
+    fn get_mut(&mut self, position: Position) -> Option<&mut T>;
+
+    /// Stores the value at the position, returning the value that was there before
+    fn insert(&mut self, position: Position, value: T) -> Option<T>;
+
+    /// The smallest bounds containing every cell, `None` when there are no cells
+    fn bounds(&self) -> Option<Bounds>;
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (Position, &'a T)> + 'a>;
+
+    fn iter_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = (Position, &'a mut T)> + 'a>;
+}
+
+/// A rectangle of cells starting at `(0, 0)` where every position within the bounds has a cell
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dense<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Dense<T> {
+    pub(crate) fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "Cell count must be width * height"
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a zero chunk size even when there are no cells
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub(crate) fn resize(&mut self, width: usize, height: usize, value: T)
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(width * height);
+        let mut old_cells = mem::take(&mut self.cells).into_iter();
+        for _ in 0..height.min(self.height) {
+            let row: Vec<_> = old_cells.by_ref().take(self.width).collect();
+            cells.extend(row.into_iter().take(width));
+            cells.extend((self.width..width).map(|_| value.clone()));
+        }
+        cells.resize(width * height, value);
+        self.width = width;
+        self.height = height;
+        self.cells = cells;
+    }
+
+    fn index_of(&self, (x, y): Position) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    fn position_of(&self, index: usize) -> Position {
+        (index % self.width, index / self.width)
+    }
+}
+
+impl<T> Storage<T> for Dense<T> {
+    fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(move |i| &mut self.cells[i])
+    }
+
+    /// Panics if the position is outside of the grid, dense grids have to be resized instead
+    fn insert(&mut self, position: Position, value: T) -> Option<T> {
+        let index = self
+            .index_of(position)
+            .unwrap_or_else(|| panic!("Position {:?} is out of bounds", position));
+        Some(mem::replace(&mut self.cells[index], value))
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        if self.cells.is_empty() {
+            None
+        } else {
+            Some(Bounds::new((0, 0), (self.width - 1, self.height - 1)))
+        }
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (Position, &'a T)> + 'a> {
+        Box::new(
+            self.cells
+                .iter()
+                .enumerate()
+                .map(move |(i, cell)| (self.position_of(i), cell)),
+        )
+    }
+
+    fn iter_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = (Position, &'a mut T)> + 'a> {
+        let width = self.width;
+        Box::new(
+            self.cells
+                .iter_mut()
+                .enumerate()
+                .map(move |(i, cell)| ((i % width, i / width), cell)),
+        )
+    }
+}
+
+/// Cells at arbitrary positions, only the positions that have been inserted have a cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sparse<T> {
+    cells: HashMap<Position, T>,
+}
+
+impl<T> Default for Sparse<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Storage<T> for Sparse<T> {
+    fn get(&self, position: Position) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.cells.get_mut(&position)
+    }
+
+    fn insert(&mut self, position: Position, value: T) -> Option<T> {
+        self.cells.insert(position, value)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        let mut positions = self.cells.keys();
+        let first = *positions.next()?;
+        Some(positions.fold(Bounds::new(first, first), |bounds, &p| bounds.including(p)))
+    }
+
+    /// Cells are visited in no particular order
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (Position, &'a T)> + 'a> {
+        Box::new(self.cells.iter().map(|(&p, cell)| (p, cell)))
+    }
+
+    fn iter_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = (Position, &'a mut T)> + 'a> {
+        Box::new(self.cells.iter_mut().map(|(&p, cell)| (p, cell)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_resizes_dense_storage() {
+        let mut dense = Dense::from_cells(2, 2, vec![1, 2, 3, 4]);
+        dense.resize(3, 3, 0);
+        assert_eq!(dense.cells, vec![1, 2, 0, 3, 4, 0, 0, 0, 0]);
+        dense.resize(1, 2, 0);
+        assert_eq!(dense.cells, vec![1, 3]);
+    }
+
+    #[test]
+    fn it_finds_sparse_bounds() {
+        let mut sparse = Sparse::default();
+        assert_eq!(sparse.bounds(), None);
+        sparse.insert((500, 0), 'a');
+        sparse.insert((495, 13), 'b');
+        sparse.insert((506, 2), 'c');
+        assert_eq!(sparse.bounds(), Some(Bounds::new((495, 0), (506, 13))));
+    }
+}
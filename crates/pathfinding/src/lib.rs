@@ -0,0 +1,214 @@
+//! Searches over graphs given by a function returning the neighbors of each state
+//!
+//! Neighbors are explored in the order they are returned and ties between equally good states
+//! are broken by the smaller state, so puzzles with rules like reading order can rely on which
+//! of several shortest paths is found.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A path from a start state to a goal state, both included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    pub states: Vec<S>,
+    pub cost: u64,
+}
+
+/// Finds a path with the fewest steps to a goal state
+///
+/// Every step costs 1, the first path found to each state is kept so neighbors should be
+/// returned in the preferred order.
+pub fn bfs<S, FN, IN, FG>(start: S, mut neighbors: FN, mut is_goal: FG) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FG: FnMut(&S) -> bool,
+{
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(start.clone());
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            let states = reconstruct_path(&parents, state);
+            let cost = states.len() as u64 - 1;
+            return Some(Path { states, cost });
+        }
+        for next in neighbors(&state) {
+            if next == start || parents.contains_key(&next) {
+                continue;
+            }
+            parents.insert(next.clone(), state.clone());
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
+/// Number of steps to every state reachable from the start, including the start itself
+pub fn bfs_distances<S, FN, IN>(start: S, mut neighbors: FN) -> HashMap<S, u64>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::new();
+    distances.insert(start.clone(), 0);
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    while let Some(state) = queue.pop_front() {
+        let distance = distances[&state] + 1;
+        for next in neighbors(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// Finds a lowest cost path to a goal state, neighbors are returned with the cost of the step
+pub fn dijkstra<S, FN, IN, FG>(start: S, neighbors: FN, is_goal: FG) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash + Ord,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, u64)>,
+    FG: FnMut(&S) -> bool,
+{
+    a_star(start, neighbors, |_| 0, is_goal)
+}
+
+/// Finds a lowest cost path to a goal state, guided by a heuristic
+///
+/// The heuristic must never overestimate the remaining cost for the path to be the cheapest.
+/// States with the same estimated cost are expanded smallest first.
+pub fn a_star<S, FN, IN, FH, FG>(
+    start: S,
+    mut neighbors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash + Ord,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, u64)>,
+    FH: FnMut(&S) -> u64,
+    FG: FnMut(&S) -> bool,
+{
+    let mut costs = HashMap::new();
+    costs.insert(start.clone(), 0);
+    let mut parents = HashMap::new();
+    let mut open = BinaryHeap::new();
+    open.push(Reverse((heuristic(&start), start, 0)));
+    while let Some(Reverse((_, state, cost))) = open.pop() {
+        if cost > costs[&state] {
+            // An outdated entry for a state that has since been reached more cheaply
+            continue;
+        }
+        if is_goal(&state) {
+            let states = reconstruct_path(&parents, state);
+            return Some(Path { states, cost });
+        }
+        for (next, step_cost) in neighbors(&state) {
+            let next_cost = cost + step_cost;
+            if costs.get(&next).is_some_and(|&c| c <= next_cost) {
+                continue;
+            }
+            costs.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), state.clone());
+            let estimate = next_cost + heuristic(&next);
+            open.push(Reverse((estimate, next, next_cost)));
+        }
+    }
+    None
+}
+
+fn reconstruct_path<S>(parents: &HashMap<S, S>, goal: S) -> Vec<S>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut path = vec![goal];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A small maze with `#` walls, addressed by `(x, y)`
+    const MAZE: [&str; 4] = ["..#..", "..#..", ".....", "#...."];
+
+    fn maze_neighbors(&(x, y): &(usize, usize)) -> Vec<(usize, usize)> {
+        let mut result = vec![];
+        if y > 0 {
+            result.push((x, y - 1));
+        }
+        if x > 0 {
+            result.push((x - 1, y));
+        }
+        result.push((x + 1, y));
+        result.push((x, y + 1));
+        result
+            .into_iter()
+            .filter(|&(x, y)| MAZE.get(y).and_then(|row| row.as_bytes().get(x)) == Some(&b'.'))
+            .collect()
+    }
+
+    #[test]
+    fn it_finds_shortest_paths_with_bfs() {
+        let path = bfs((0, 0), maze_neighbors, |&p| p == (4, 0)).unwrap();
+        assert_eq!(path.cost, 8);
+        assert_eq!(path.states.first(), Some(&(0, 0)));
+        assert_eq!(path.states.last(), Some(&(4, 0)));
+        // Neighbors are returned in reading order, so the path stays as high as it can
+        assert_eq!(&path.states[1..3], &[(1, 0), (1, 1)]);
+        assert_eq!(bfs((0, 0), maze_neighbors, |&p| p == (0, 3)), None);
+    }
+
+    #[test]
+    fn it_finds_every_distance_with_bfs() {
+        let distances = bfs_distances((0, 0), maze_neighbors);
+        assert_eq!(distances.len(), 17);
+        assert_eq!(distances[&(0, 0)], 0);
+        assert_eq!(distances[&(4, 3)], 7);
+        assert!(!distances.contains_key(&(0, 3)));
+    }
+
+    #[test]
+    fn it_finds_cheapest_paths() {
+        // Going straight from 0 to 3 is more expensive than going around
+        let edges = |&n: &u32| match n {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+        let expected = Path {
+            states: vec![0, 1, 2, 3],
+            cost: 3,
+        };
+        assert_eq!(dijkstra(0, edges, |&n| n == 3), Some(expected.clone()));
+        assert_eq!(
+            a_star(0, edges, |&n| 3 - n as u64, |&n| n == 3),
+            Some(expected)
+        );
+        assert_eq!(dijkstra(1, edges, |&n| n == 0), None);
+    }
+
+    #[test]
+    fn it_breaks_ties_with_the_smallest_state() {
+        let edges = |&n: &u32| match n {
+            0 => vec![(2, 1), (1, 1)],
+            1 | 2 => vec![(3, 1)],
+            _ => vec![],
+        };
+        let path = dijkstra(0, edges, |&n| n == 3).unwrap();
+        assert_eq!(path.states, vec![0, 1, 3]);
+    }
+}
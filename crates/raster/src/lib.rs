@@ -0,0 +1,277 @@
+//! Images of grid states with a color per tile, written as PPM or PNG
+
+use grid::{Bounds, Grid, Position, Storage};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// An RGB color
+pub type Color = [u8; 3];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            _ => None,
+        }
+    }
+
+    /// The format named by the path's extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        Self::from_name(&path.extension()?.to_string_lossy().to_lowercase())
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+        }
+    }
+}
+
+/// A rectangle of pixels stored in reading order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Draws every position within the grid's bounds as a pixel
+    pub fn from_grid<T, S, F>(grid: &Grid<T, S>, palette: F) -> Self
+    where
+        S: Storage<T>,
+        F: FnMut(Position, Option<&T>) -> Color,
+    {
+        match grid.bounds() {
+            Some(bounds) => Self::from_area(grid, bounds, palette),
+            None => Self::new(0, 0, [0, 0, 0]),
+        }
+    }
+
+    /// Draws every position within the bounds as a pixel, the bounds may extend past the grid's
+    /// cells
+    pub fn from_area<T, S, F>(grid: &Grid<T, S>, bounds: Bounds, mut palette: F) -> Self
+    where
+        S: Storage<T>,
+        F: FnMut(Position, Option<&T>) -> Color,
+    {
+        let mut pixels = Vec::with_capacity(bounds.width() * bounds.height());
+        for y in bounds.min.1..=bounds.max.1 {
+            for x in bounds.min.0..=bounds.max.0 {
+                pixels.push(palette((x, y), grid.get((x, y))));
+            }
+        }
+        Self {
+            width: bounds.width(),
+            height: bounds.height(),
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Position) -> Option<Color> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Colors the pixel, positions outside of the image are ignored
+    pub fn set(&mut self, (x, y): Position, color: Color) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    /// The image with each pixel drawn as a `scale` by `scale` square
+    pub fn scaled(&self, scale: usize) -> Self {
+        let mut pixels = Vec::with_capacity(self.pixels.len() * scale * scale);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            for _ in 0..scale {
+                for &color in row {
+                    pixels.extend((0..scale).map(|_| color));
+                }
+            }
+        }
+        Self {
+            width: self.width * scale,
+            height: self.height * scale,
+            pixels,
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().cloned().collect()
+    }
+
+    /// Writes the image as a binary PPM
+    pub fn write_ppm<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        write!(output, "P6\n{} {}\n255\n", self.width, self.height)?;
+        output.write_all(&self.bytes())
+    }
+
+    pub fn write_png<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(output, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.bytes())
+            .map_err(io::Error::other)
+    }
+
+    pub fn write<W: Write>(&self, format: Format, output: &mut W) -> io::Result<()> {
+        match format {
+            Format::Ppm => self.write_ppm(output),
+            Format::Png => self.write_png(output),
+        }
+    }
+
+    /// Writes the image to a `.ppm` or `.png` file
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let format = Format::from_path(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is not a .ppm or .png path", path.display()),
+            )
+        })?;
+        let mut output = BufWriter::new(File::create(path)?);
+        self.write(format, &mut output)?;
+        output.flush()
+    }
+}
+
+/// Something that can be drawn as an image, with a pixel per tile
+pub trait Draw {
+    fn draw(&self) -> Image;
+}
+
+/// Writes images as numbered frames like `frame-00001.png`, which `ffmpeg -i frame-%05d.png`
+/// turns into a video
+#[derive(Debug)]
+pub struct FrameWriter {
+    dir: PathBuf,
+    format: Format,
+    frames: usize,
+}
+
+impl FrameWriter {
+    /// Writes the frames to `dir`, creating it if needed
+    pub fn new(dir: &Path, format: Format) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            format,
+            frames: 0,
+        })
+    }
+
+    /// Path of the frame with the 1-based `number`
+    pub fn frame_path(&self, number: usize) -> PathBuf {
+        self.dir
+            .join(format!("frame-{:05}.{}", number, self.format.extension()))
+    }
+
+    /// Writes the next frame, returning its path
+    pub fn write(&mut self, image: &Image) -> io::Result<PathBuf> {
+        self.frames += 1;
+        let path = self.frame_path(self.frames);
+        let mut output = BufWriter::new(File::create(&path)?);
+        image.write(self.format, &mut output)?;
+        output.flush()?;
+        Ok(path)
+    }
+
+    /// Number of frames written
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    const CLAY: Color = [128, 64, 0];
+    const SAND: Color = [240, 220, 160];
+
+    fn sample_image() -> Image {
+        let grid = Grid::from_char_map("#.\n.#", |_, c| c == '#');
+        Image::from_grid(
+            &grid,
+            |_, clay| if clay == Some(&true) { CLAY } else { SAND },
+        )
+    }
+
+    #[test]
+    fn it_draws_grids() {
+        let image = sample_image();
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert_eq!(image.get((0, 0)), Some(CLAY));
+        assert_eq!(image.get((1, 0)), Some(SAND));
+        assert_eq!(image.get((2, 0)), None);
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (4, 4));
+        assert_eq!(scaled.get((1, 1)), Some(CLAY));
+        assert_eq!(scaled.get((2, 1)), Some(SAND));
+        assert_eq!(scaled.get((3, 3)), Some(CLAY));
+    }
+
+    #[test]
+    fn it_writes_ppm_and_png() {
+        let image = sample_image();
+        let mut ppm = vec![];
+        image.write_ppm(&mut ppm).unwrap();
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([CLAY, SAND, SAND, CLAY].iter().flatten());
+        assert_eq!(ppm, expected);
+
+        let mut png = vec![];
+        image.write_png(&mut png).unwrap();
+        let mut reader = png::Decoder::new(&png[..]).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(pixels, image.bytes());
+    }
+
+    #[test]
+    fn it_writes_numbered_frames() {
+        assert_eq!(Format::from_path(Path::new("world.PNG")), Some(Format::Png));
+        assert_eq!(Format::from_path(Path::new("world.txt")), None);
+        let dir = env::temp_dir().join(format!("raster-test-{}", std::process::id()));
+        let mut frames = FrameWriter::new(&dir, Format::Ppm).unwrap();
+        frames.write(&sample_image()).unwrap();
+        let path = frames.write(&sample_image()).unwrap();
+        assert_eq!(path, dir.join("frame-00002.ppm"));
+        assert_eq!(frames.frames(), 2);
+        assert!(dir.join("frame-00001.ppm").is_file());
+        assert!(sample_image().save(&dir.join("image.gif")).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,96 @@
+use common::{end_of_input, lines, ParseResult, Solution};
+use std::collections::HashSet;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        sum(input)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        find_repeat_result(input)
+    }
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<i32>> {
+    let mut changes = vec![];
+    for line in lines(input) {
+        for change in line.text.split_whitespace() {
+            changes.push(line.parse(change, "a frequency change like +1")?);
+        }
+    }
+    if changes.is_empty() {
+        return Err(end_of_input(input, "a frequency change like +1"));
+    }
+    Ok(changes)
+}
+
+pub fn sum(numbers: &[i32]) -> i32 {
+    numbers.iter().sum()
+}
+
+pub fn find_repeat_result(numbers: &[i32]) -> i32 {
+    let mut past_results = HashSet::new();
+    let mut result = 0;
+    past_results.insert(result);
+    loop {
+        for number in numbers {
+            result += number;
+            let is_repeat = !past_results.insert(result);
+            if is_repeat {
+                return result;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn sum_returns_correct_result() {
+        assert_eq!(sum(&[2, 2]), 4);
+        assert_eq!(sum(&[2, 2, -4, 0, 1]), 1);
+    }
+
+    #[test]
+    fn find_repeat_result_returns_correct_result() {
+        assert_eq!(find_repeat_result(&[1, -1]), 0);
+        assert_eq!(find_repeat_result(&[3, 3, 4, -2, -4]), 10);
+        assert_eq!(find_repeat_result(&[-6, 3, 8, 5, -6]), 5);
+        assert_eq!(find_repeat_result(&[7, 7, -2, -7, -4]), 14);
+    }
+
+    #[test]
+    fn it_rejects_blank_input() {
+        assert_eq!(
+            parse_input("\n").unwrap_err().to_string(),
+            "line 2, column 1: expected a frequency change like +1, found nothing"
+        );
+    }
+
+    fn frequency_changes() -> impl Strategy<Value = Vec<i32>> {
+        prop::collection::vec(-1_000_000..1_000_000, 1..100)
+    }
+
+    proptest! {
+        #[test]
+        fn it_parses_generated_frequency_changes(changes in frequency_changes()) {
+            let input: String = changes.iter().map(|c| format!("{:+}\n", c)).collect();
+            prop_assert_eq!(parse_input(&input).unwrap(), changes);
+        }
+    }
+}
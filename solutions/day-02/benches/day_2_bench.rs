@@ -0,0 +1,27 @@
+use bench::bench_solution;
+use common::Solution;
+use criterion::{criterion_group, criterion_main, Criterion, Fun};
+use day_2::{find_similar_id_match, with_cartesian, Day2};
+
+const INPUT: &str = include_str!("../input");
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_solution::<Day2>(c, 2, INPUT);
+    let ids = Day2::parse(INPUT).unwrap();
+    let with_loops = Fun::new("with loops", |b, ids: &Vec<String>| {
+        let ids: Vec<_> = ids.iter().map(String::as_str).collect();
+        b.iter(|| find_similar_id_match(&ids))
+    });
+    let with_cartesian = Fun::new("with cartesian", |b, ids: &Vec<String>| {
+        let ids: Vec<_> = ids.iter().map(String::as_str).collect();
+        b.iter(|| with_cartesian::find_similar_id_match(&ids))
+    });
+    c.bench_functions(
+        "find similar id match",
+        vec![with_loops, with_cartesian],
+        ids,
+    );
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
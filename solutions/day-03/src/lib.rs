@@ -0,0 +1,175 @@
+use common::{end_of_input, lines, Line, ParseResult, Solution};
+use geometry::{BoundingBox, Point, Point2};
+use std::collections::HashMap;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<FabricClaim>;
+    type PartOne = usize;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        count_overlapping_fabric_claim_units(input)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        find_fabric_claim_with_no_overlap(input).id
+    }
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<FabricClaim>> {
+    let claims: Vec<_> = lines(input)
+        .filter(|line| !line.text.trim().is_empty())
+        .map(parse_claim)
+        .collect::<ParseResult<_>>()?;
+    if claims.is_empty() {
+        return Err(end_of_input(input, "a claim"));
+    }
+    Ok(claims)
+}
+
+/// Parses a claim like `#1 @ 1,3: 4x4`
+fn parse_claim(line: Line) -> ParseResult<FabricClaim> {
+    let mut cursor = line.cursor();
+    cursor.literal("#")?;
+    let id = cursor.number("a claim id")?;
+    cursor.literal(" @ ")?;
+    let x = cursor.number("a left edge")?;
+    cursor.literal(",")?;
+    let y = cursor.number("a top edge")?;
+    cursor.literal(": ")?;
+    let width = cursor.number("a width")?;
+    cursor.literal("x")?;
+    let height = cursor.number("a height")?;
+    cursor.end()?;
+    Ok(FabricClaim {
+        id,
+        coordinates: Point::new([x, y]),
+        width,
+        height,
+    })
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct FabricClaim {
+    pub id: u32,
+    pub coordinates: Point2,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl FabricClaim {
+    fn area(&self) -> BoundingBox<2> {
+        let size = Point::new([i64::from(self.width), i64::from(self.height)]);
+        BoundingBox::new(self.coordinates, self.coordinates + size - Point::splat(1))
+    }
+}
+
+pub fn count_overlapping_fabric_claim_units(claims: &[FabricClaim]) -> usize {
+    get_claim_count_by_coordinate_map(claims)
+        .values()
+        .filter(|value| **value > 1)
+        .count()
+}
+
+pub fn find_fabric_claim_with_no_overlap(claims: &[FabricClaim]) -> &FabricClaim {
+    let claim_count_by_coordinate = get_claim_count_by_coordinate_map(claims);
+    claims
+        .iter()
+        .find(|claim| {
+            claim
+                .area()
+                .points()
+                .all(|p| claim_count_by_coordinate[&p] == 1)
+        })
+        .expect("No solution found")
+}
+
+fn get_claim_count_by_coordinate_map(claims: &[FabricClaim]) -> HashMap<Point2, u32> {
+    claims.iter().fold(HashMap::new(), |mut map, claim| {
+        for p in claim.area().points() {
+            *map.entry(p).or_insert(0) += 1;
+        }
+        map
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::ParseError;
+
+    #[test]
+    fn it_parses_input_correctly() {
+        let sample_input = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2";
+        assert_eq!(parse_input(sample_input).unwrap(), get_sample_claims());
+    }
+
+    #[test]
+    fn it_reports_where_a_claim_is_malformed() {
+        assert_eq!(
+            parse_input("#1 @ 1,3: 4x4\n#2 @ 3,1 4x4").unwrap_err(),
+            ParseError::new(2, 9, "\": \"", " ")
+        );
+    }
+
+    #[test]
+    fn it_counts_overlapping_fabric_claim_units_correctly() {
+        assert_eq!(
+            count_overlapping_fabric_claim_units(get_sample_claims().as_ref()),
+            4
+        );
+        assert_eq!(
+            count_overlapping_fabric_claim_units(&get_real_input_claims()),
+            100_261
+        );
+    }
+
+    #[test]
+    fn it_finds_correct_fabric_claim_with_no_overlap() {
+        assert_eq!(
+            find_fabric_claim_with_no_overlap(get_sample_claims().as_ref()).id,
+            3
+        );
+        assert_eq!(
+            find_fabric_claim_with_no_overlap(&get_real_input_claims()).id,
+            251
+        );
+    }
+
+    fn get_sample_claims() -> [FabricClaim; 3] {
+        [
+            FabricClaim {
+                id: 1,
+                coordinates: Point::new([1, 3]),
+                width: 4,
+                height: 4,
+            },
+            FabricClaim {
+                id: 2,
+                coordinates: Point::new([3, 1]),
+                width: 4,
+                height: 4,
+            },
+            FabricClaim {
+                id: 3,
+                coordinates: Point::new([5, 5]),
+                width: 2,
+                height: 2,
+            },
+        ]
+    }
+
+    // relies on correct parse_input
+    fn get_real_input_claims() -> Vec<FabricClaim> {
+        let real_input = include_str!("../input");
+        parse_input(real_input).unwrap()
+    }
+}
@@ -0,0 +1,128 @@
+use common::{end_of_input, section_lines, ParseResult, Solution};
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = String;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(String::from(parse_input(input)?))
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        find_part_one_solution(input)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        find_part_two_solution(input)
+    }
+}
+
+pub fn parse_input(input: &str) -> ParseResult<&str> {
+    let polymer = input.trim();
+    for line in section_lines(input, polymer) {
+        if let Some(offset) = line.text.find(|c: char| !c.is_ascii_alphabetic()) {
+            return Err(line.error(offset, "a unit letter"));
+        }
+    }
+    if polymer.is_empty() {
+        return Err(end_of_input(input, "a polymer"));
+    }
+    Ok(polymer)
+}
+
+pub fn find_part_one_solution(polymer: &str) -> usize {
+    fully_react_polymer(polymer).len()
+}
+
+pub fn find_part_two_solution(polymer: &str) -> usize {
+    (b'a'..=b'z')
+        .map(|removed_unit| {
+            let remaining_units = polymer
+                .bytes()
+                .filter(|b| b.to_ascii_lowercase() != removed_unit)
+                .collect();
+            let edited_polymer = String::from_utf8(remaining_units).unwrap();
+            fully_react_polymer(&edited_polymer).len()
+        })
+        .min()
+        .unwrap()
+}
+
+fn fully_react_polymer(polymer: &str) -> String {
+    let mut current_polymer = String::from(polymer);
+    let mut previous_polymer: Option<String> = None;
+    while previous_polymer.is_none() || *previous_polymer.unwrap() != current_polymer {
+        previous_polymer = Some(current_polymer.clone());
+        current_polymer = current_polymer
+            .chars()
+            .fold(String::new(), |mut result, unit| {
+                match result.chars().last() {
+                    Some(previous_unit) if is_reacting_pair(unit, previous_unit) => {
+                        result.pop();
+                    }
+                    _ => {
+                        result.push(unit);
+                    }
+                };
+                result
+            });
+    }
+    current_polymer
+}
+
+fn is_reacting_pair(a: char, b: char) -> bool {
+    a != b && a.eq_ignore_ascii_case(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn it_removes_newline() {
+        assert_eq!(parse_input("aA\n").unwrap().len(), 2);
+        assert_eq!(
+            parse_input("\naA1").unwrap_err().to_string(),
+            "line 2, column 3: expected a unit letter, found \"1\""
+        );
+    }
+
+    #[test]
+    fn it_finds_correct_part_one_solution() {
+        let sample_input = "dabAcCaCBAcCcaDA";
+        assert_eq!(find_part_one_solution(sample_input), 10);
+    }
+
+    #[test]
+    fn it_finds_correct_part_two_solution() {
+        let sample_input = "dabAcCaCBAcCcaDA";
+        assert_eq!(find_part_two_solution(sample_input), 4);
+    }
+
+    /// Polymers made of a few unit types, so that many of their units react
+    fn polymers() -> impl Strategy<Value = String> {
+        "[abcABC]{1,200}"
+    }
+
+    proptest! {
+        #[test]
+        fn it_parses_generated_polymers(polymer in polymers()) {
+            let input = format!("{}\n", polymer);
+            prop_assert_eq!(parse_input(&input).unwrap(), polymer.as_str());
+        }
+
+        #[test]
+        fn it_reacts_polymers_until_no_pair_is_left(polymer in polymers()) {
+            let reacted = fully_react_polymer(&polymer);
+            let units: Vec<_> = reacted.chars().collect();
+            prop_assert!(units.windows(2).all(|pair| !is_reacting_pair(pair[0], pair[1])));
+            prop_assert_eq!(fully_react_polymer(&reacted), reacted.clone());
+            prop_assert!(find_part_two_solution(&polymer) <= reacted.len());
+        }
+    }
+}
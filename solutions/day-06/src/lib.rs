@@ -0,0 +1,334 @@
+use common::{end_of_input, lines, ParseResult, Solution};
+use geometry::{BoundingBox, Point, Point2};
+use raster::{Color, Draw, Image};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+
+pub const MAX_DISTANCE: usize = 10_000;
+const UPPERCASE_LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWERCASE_LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+const COORDINATE_COLOR: Color = [0, 0, 0];
+const TIED_COLOR: Color = [255, 255, 255];
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Point2>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        find_part_one_solution(input)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        find_part_two_solution(input, MAX_DISTANCE)
+    }
+}
+
+#[derive(Debug)]
+struct GridCoordinate {
+    id: usize,
+    point: Point2,
+}
+
+struct Grid {
+    data: grid::Grid<Option<usize>>,
+    x_offset: i64,
+    y_offset: i64,
+    coordinates: Vec<GridCoordinate>,
+}
+
+impl Grid {
+    fn from_points(points: &[Point2]) -> Self {
+        let bounds = BoundingBox::from_points(points.iter().cloned()).unwrap();
+        let mut data = grid::Grid::new(bounds.width() as usize, bounds.height() as usize, None);
+        for (index, p) in points.iter().enumerate() {
+            let offset = *p - bounds.min;
+            data[(offset.x() as usize, offset.y() as usize)] = Some(index);
+        }
+        let coordinates = points
+            .iter()
+            .enumerate()
+            .map(|(index, &point)| GridCoordinate { id: index, point })
+            .collect();
+        Grid {
+            data,
+            x_offset: bounds.min.x(),
+            y_offset: bounds.min.y(),
+            coordinates,
+        }
+    }
+
+    fn fill_areas(&mut self) {
+        let (x_offset, y_offset) = (self.x_offset, self.y_offset);
+        for ((j, i), value) in self.data.iter_mut() {
+            if value.is_some() {
+                continue;
+            }
+            let current_point = Point::new([j as i64 + x_offset, i as i64 + y_offset]);
+            let closest_coordinate = self.coordinates.iter().min_by_strict(|a, b| {
+                a.point
+                    .manhattan_distance(&current_point)
+                    .cmp(&b.point.manhattan_distance(&current_point))
+            });
+            *value = closest_coordinate.map(|c| c.id);
+        }
+    }
+
+    fn has_area_reaching_edge(&self, c: &GridCoordinate) -> bool {
+        let bounds = self.data.bounds().unwrap();
+        let edge_points: HashSet<_> = self
+            .data
+            .iter()
+            .filter(|&(p, _)| bounds.is_on_edge(p))
+            .filter_map(|(_, v)| *v) // Filter out None and map Some(value) to value
+            .collect();
+        edge_points.contains(&c.id)
+    }
+
+    fn to_point(&self, (x, y): grid::Position) -> Point2 {
+        Point::new([x as i64 + self.x_offset, y as i64 + self.y_offset])
+    }
+
+    fn has_coordinate_at(&self, point: &Point2) -> bool {
+        self.coordinates
+            .iter()
+            .map(|c| c.point)
+            .any(|p| *point == p)
+    }
+
+    fn count_points_with_id(&self, id: usize) -> usize {
+        self.data
+            .values()
+            .filter(|&&value| value == Some(id))
+            .count()
+    }
+
+    fn count_points_with_max_total_coordinate_distance(&self, max_distance: usize) -> usize {
+        let mut count = 0;
+        for (position, _) in self.data.iter() {
+            let current_point = self.to_point(position);
+            let total_coordinate_distance: u64 = self
+                .coordinates
+                .iter()
+                .map(|c| current_point.manhattan_distance(&c.point))
+                .sum();
+            if total_coordinate_distance < max_distance as u64 {
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+impl fmt::Debug for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Grid {{ x_offset: {:?}, y_offset: {:?}, coordinates: {:?}, data: \n\n  ",
+            self.x_offset, self.y_offset, self.coordinates
+        )?;
+        for column_number in self.x_offset..self.x_offset + self.data.width() as i64 {
+            write!(f, "{} ", column_number)?;
+        }
+        writeln!(f)?;
+        for (i, row) in self.data.rows().enumerate() {
+            write!(f, "{} ", i as i64 + self.y_offset)?;
+            for (j, value) in row.iter().enumerate() {
+                let point = self.to_point((j, i));
+                write!(
+                    f,
+                    "{} ",
+                    match value {
+                        Some(i) => {
+                            let letters = if self.has_coordinate_at(&point) {
+                                UPPERCASE_LETTERS
+                            } else {
+                                LOWERCASE_LETTERS
+                            };
+                            letters.chars().nth(*i).unwrap_or('*')
+                        }
+                        None => '.',
+                    }
+                )?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "\n}}")
+    }
+}
+
+impl Draw for Grid {
+    fn draw(&self) -> Image {
+        Image::from_grid(&self.data, |position, value| match value {
+            Some(Some(_)) if self.has_coordinate_at(&self.to_point(position)) => COORDINATE_COLOR,
+            Some(Some(id)) => area_color(*id),
+            _ => TIED_COLOR,
+        })
+    }
+}
+
+/// A color for the area of a coordinate, neighboring ids get very different colors
+fn area_color(id: usize) -> Color {
+    let spread = |step: usize, offset: usize| ((id * step + offset) % 200 + 40) as u8;
+    [spread(97, 0), spread(57, 80), spread(151, 160)]
+}
+
+/// Draws the area closest to each coordinate in its own color
+pub fn draw_areas(points: &[Point2]) -> Image {
+    let mut grid = Grid::from_points(points);
+    grid.fill_areas();
+    grid.draw()
+}
+
+trait MinByStrictExt: Iterator {
+    /// Returns the element that has the minimum value.
+    ///
+    /// If several elements are equally minimum, None is returned.
+    /// If the iterator is empty, None is returned.
+    #[allow(dead_code)]
+    fn min_strict(self) -> Option<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        self.min_by_strict(|a, b| a.cmp(b))
+    }
+
+    /// Returns the element that gives the minimum value with respect to the specified comparison function.
+    ///
+    /// If several elements are equally minimum, None is returned.
+    /// If the iterator is empty, None is returned.
+    fn min_by_strict<F>(self, mut compare: F) -> Option<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+        Self: Sized,
+    {
+        let (min, count) = self.fold((None, 0), |(current_min, count), item| match current_min {
+            None => (Some(item), 1),
+            Some(min) => match compare(&item, &min) {
+                Ordering::Less => (Some(item), 1),
+                Ordering::Equal => (Some(min), count + 1),
+                Ordering::Greater => (Some(min), count),
+            },
+        });
+        if count == 1 {
+            min
+        } else {
+            None
+        }
+    }
+}
+
+impl<I: Iterator> MinByStrictExt for I {}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<Point2>> {
+    let points: Vec<_> = lines(input)
+        .filter(|line| !line.text.trim().is_empty())
+        .map(|line| {
+            let mut cursor = line.cursor();
+            let x = cursor.number("an x coordinate")?;
+            cursor.literal(", ")?;
+            let y = cursor.number("a y coordinate")?;
+            cursor.end()?;
+            Ok(Point::new([x, y]))
+        })
+        .collect::<ParseResult<_>>()?;
+    if points.is_empty() {
+        return Err(end_of_input(input, "a coordinate"));
+    }
+    Ok(points)
+}
+
+pub fn find_part_one_solution(points: &[Point2]) -> usize {
+    let mut grid = Grid::from_points(points);
+    grid.fill_areas();
+    grid.coordinates
+        .iter()
+        .filter(|c| !grid.has_area_reaching_edge(c))
+        .map(|c| grid.count_points_with_id(c.id))
+        .max()
+        .expect("No solution found")
+}
+
+pub fn find_part_two_solution(points: &[Point2], max_distance: usize) -> usize {
+    let grid = Grid::from_points(points);
+    grid.count_points_with_max_total_coordinate_distance(max_distance)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../sample-input");
+
+    #[test]
+    fn it_parses_input_correctly() {
+        assert_eq!(parse_input(SAMPLE_INPUT).unwrap(), get_sample_input());
+    }
+
+    #[test]
+    fn it_renders_the_filled_areas() {
+        let mut grid = Grid::from_points(&get_sample_input());
+        grid.fill_areas();
+        common::assert_snapshot(
+            env!("CARGO_MANIFEST_DIR"),
+            "sample-input",
+            &format!("{:?}", grid),
+        );
+    }
+
+    #[test]
+    fn it_finds_correct_part_one_solution() {
+        let sample_input = get_sample_input();
+        assert_eq!(find_part_one_solution(&sample_input), 17);
+    }
+
+    #[test]
+    fn it_finds_correct_part_two_solution() {
+        let sample_input = get_sample_input();
+        assert_eq!(find_part_two_solution(&sample_input, 32), 16);
+    }
+
+    #[test]
+    fn point_distance_to_returns_correct_result() {
+        let a = Point::new([0, 0]);
+        let b = Point::new([1, 1]);
+        let c = Point::new([5, 10]);
+        assert_eq!(a.manhattan_distance(&b), 2);
+        assert_eq!(a.manhattan_distance(&c), 15);
+        assert_eq!(b.manhattan_distance(&a), 2);
+        assert_eq!(b.manhattan_distance(&c), 13);
+    }
+
+    #[test]
+    fn min_by_strict_returns_correct_result() {
+        let a = [1, 2, 3];
+        let b = [1, 1, 3];
+        let c: Vec<i32> = vec![];
+        let d = [3, 3, 1];
+        assert_eq!(a.iter().min_by_strict(|a, b| a.cmp(b)), Some(&1));
+        assert_eq!(b.iter().min_by_strict(|a, b| a.cmp(b)), None);
+        assert_eq!(c.iter().min_by_strict(|a, b| a.cmp(b)), None);
+        assert_eq!(d.iter().min_by_strict(|a, b| a.cmp(b)), Some(&1));
+    }
+
+    fn get_sample_input() -> [Point2; 6] {
+        [
+            Point::new([1, 1]),
+            Point::new([1, 6]),
+            Point::new([8, 3]),
+            Point::new([3, 4]),
+            Point::new([5, 5]),
+            Point::new([8, 9]),
+        ]
+    }
+}
@@ -0,0 +1,226 @@
+use common::{end_of_input, lines, Line, ParseResult, Solution};
+use std::collections::HashSet;
+use std::hash::Hash;
+
+pub const WORKERS: usize = 5;
+pub const BASE_TIME: u32 = 60;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Graph<char>;
+    type PartOne = String;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        solve_part_one(input)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        solve_part_two(input, WORKERS, BASE_TIME)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Graph<T>
+where
+    T: Eq + Hash,
+{
+    nodes: HashSet<T>,
+    edges: Vec<(T, T)>,
+}
+
+impl<T> Graph<T>
+where
+    T: Eq + Hash + Clone,
+{
+    fn from_edges(edges: &[(T, T)]) -> Self {
+        let nodes = edges.iter().fold(HashSet::new(), |mut acc, (a, b)| {
+            acc.insert(a.clone());
+            acc.insert(b.clone());
+            acc
+        });
+        Graph {
+            nodes,
+            edges: edges.to_vec(),
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Graph<char>> {
+    let edges: Vec<(char, char)> = lines(input)
+        .filter(|line| !line.text.trim().is_empty())
+        .map(parse_edge)
+        .collect::<ParseResult<_>>()?;
+    if edges.is_empty() {
+        return Err(end_of_input(input, "a step requirement"));
+    }
+    Ok(Graph::from_edges(&edges))
+}
+
+/// Parses a line like `Step C must be finished before step A can begin.`
+fn parse_edge(line: Line) -> ParseResult<(char, char)> {
+    let is_step = |c: char| c.is_ascii_uppercase();
+    let mut cursor = line.cursor();
+    cursor.literal("Step ")?;
+    let first = cursor.char("a step letter", is_step)?;
+    cursor.literal(" must be finished before step ")?;
+    let second = cursor.char("a step letter", is_step)?;
+    cursor.literal(" can begin.")?;
+    cursor.end()?;
+    Ok((first, second))
+}
+
+// Kahn's algorithm for topological sort
+pub fn solve_part_one(g: &Graph<char>) -> String {
+    let steps = &g.nodes;
+    let mut result = String::new();
+    let mut remaining_requirements = g.edges.clone();
+    let mut ready_steps: Vec<_> = steps
+        .iter()
+        // Filter out steps that depend on any other step (these are not ready to complete)
+        .filter(|&&step| {
+            !remaining_requirements
+                .iter()
+                .any(|&(_, dependent)| dependent == step)
+        })
+        .collect();
+    while !ready_steps.is_empty() {
+        // Complete the first (alphabetically ordered) ready step
+        let completed_step = **ready_steps.iter().min().unwrap();
+        result.push(completed_step);
+
+        remaining_requirements.retain(|&(depended, _)| depended != completed_step);
+
+        ready_steps = steps
+            .iter()
+            .filter(|&&step| {
+                // Filter out steps that have already been completed
+                !result.chars().any(|c| c == step)
+                // Filter out steps that depend on any other step (these are not ready to complete)
+                && !remaining_requirements.iter().any(|&(_, dependent)| dependent == step)
+            })
+            .collect();
+    }
+    if !remaining_requirements.is_empty() {
+        panic!("cycle detected");
+    }
+    result
+}
+
+pub fn solve_part_two(g: &Graph<char>, worker_count: usize, base_time: u32) -> u32 {
+    let steps = &g.nodes;
+    let mut time_passed = 0;
+    let mut workers: Vec<Option<(char, u32)>> = vec![None; worker_count];
+    let mut completed_steps = HashSet::new();
+    let mut remaining_requirements = g.edges.clone();
+    let mut ready_steps: Vec<_> = steps
+        .iter()
+        .filter(|&&step| {
+            // Filter out steps that depend on any other step (these are not ready to complete)
+            !remaining_requirements
+                .iter()
+                .any(|&(_, dependent)| dependent == step)
+        })
+        .collect();
+    while !ready_steps.is_empty() || workers.iter().filter(|w| w.is_some()).count() != 0 {
+        let iter = workers
+            .iter_mut()
+            .filter(|w| w.is_none())
+            .zip(ready_steps.iter());
+        for (ready_worker, &ready_step) in iter {
+            *ready_worker = Some((
+                *ready_step,
+                u32::from(*ready_step as u8 - b'A' + 1) + base_time,
+            ));
+        }
+        let t = workers
+            .iter()
+            .filter_map(|w| w.as_ref().map(|(_, t)| *t))
+            .min()
+            .unwrap();
+        let finished_workers = workers
+            .iter_mut()
+            .filter(|w| w.is_some() && w.unwrap().1 == t);
+        for finished_worker in finished_workers {
+            let completed_step = finished_worker.unwrap().0;
+            completed_steps.insert(completed_step);
+            *finished_worker = None;
+            // Filter out requirements that depend on the completed step (these have been fulfilled)
+            remaining_requirements.retain(|&(depended, _)| depended != completed_step);
+        }
+        time_passed += t;
+        workers = workers
+            .iter()
+            .map(|w| w.as_ref().map(|w| (w.0, w.1 - t)))
+            .collect();
+
+        ready_steps = steps
+            .iter()
+            .filter(|&&step| {
+                // Filter out steps that have already been completed
+                !completed_steps.contains(&step)
+                // Filter out steps that are already being worked on
+                && !workers.iter().any(|w| w.is_some() && w.unwrap().0 == step)
+                // Filter out steps that depend on any other step (these are not ready to complete)
+                && !remaining_requirements.iter().any(|&(_, dependent)| dependent == step)
+            })
+            .collect();
+        ready_steps.sort();
+    }
+    if !remaining_requirements.is_empty() {
+        panic!("cycle detected");
+    }
+    time_passed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_INPUT_STR: &str = include_str!("../sample-input");
+
+    #[test]
+    fn it_parses_input_correctly() {
+        let sample_graph = get_sample_graph();
+        assert_eq!(parse_input(SAMPLE_INPUT_STR).unwrap(), sample_graph);
+    }
+
+    #[test]
+    fn it_reports_a_missing_step() {
+        assert_eq!(
+            parse_input("Step C must be finished before step  can begin.")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 37: expected a step letter, found \" \""
+        );
+    }
+
+    #[test]
+    fn it_solves_part_one_correctly() {
+        assert_eq!(solve_part_one(&get_sample_graph()), "CABDFE");
+    }
+
+    #[test]
+    fn it_solves_part_two_correctly() {
+        assert_eq!(solve_part_two(&get_sample_graph(), 2, 0), 15);
+    }
+
+    fn get_sample_graph() -> Graph<char> {
+        Graph::from_edges(&[
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ])
+    }
+}
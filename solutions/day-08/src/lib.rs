@@ -0,0 +1,178 @@
+use common::{end_of_input, lines, ParseResult, Solution};
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Node;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        input.sum_metadata()
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        input.find_value()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Node {
+    children: Vec<Node>,
+    metadata: Vec<u32>,
+}
+
+impl Node {
+    /// Builds the node at the start of the data and returns how many numbers it used, or
+    /// `None` if the data ends too soon
+    fn from_data(data: &[u32]) -> Option<(Self, usize)> {
+        let child_count = *data.first()?;
+        let metadata_count = *data.get(1)? as usize;
+        let mut children = vec![];
+        let mut index = 2;
+        for _ in 0..child_count {
+            let (child, len) = Node::from_data(data.get(index..)?)?;
+            children.push(child);
+            index += len;
+        }
+        let metadata = data.get(index..(index + metadata_count))?.to_vec();
+        index += metadata_count;
+        Some((Node { children, metadata }, index))
+    }
+
+    pub fn sum_metadata(&self) -> u32 {
+        self.metadata.iter().sum::<u32>()
+            + self.children.iter().map(|c| c.sum_metadata()).sum::<u32>()
+    }
+
+    pub fn find_value(&self) -> u32 {
+        if self.children.is_empty() {
+            return self.metadata.iter().sum();
+        }
+        self.metadata
+            .iter()
+            .map(|&m| {
+                self.children
+                    .get(m as usize - 1)
+                    .map(|c| c.find_value())
+                    .unwrap_or(0)
+            })
+            .sum()
+    }
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Node> {
+    let mut data = vec![];
+    let mut tokens = vec![];
+    for line in lines(input) {
+        for token in line.text.split_whitespace() {
+            data.push(line.parse(token, "a number")?);
+            tokens.push((line, token));
+        }
+    }
+    let (node, len) =
+        Node::from_data(&data).ok_or_else(|| end_of_input(input, "the rest of the tree"))?;
+    match tokens.get(len) {
+        Some((line, token)) => Err(line.error(line.offset_of(token), "the end of the tree")),
+        None => Ok(node),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::ParseError;
+    use proptest::prelude::*;
+
+    const SAMPLE_INPUT: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2\n";
+    const REAL_INPUT: &str = include_str!("../input");
+
+    #[test]
+    fn it_parses_input_correctly() {
+        assert_eq!(parse_input(SAMPLE_INPUT).unwrap(), get_sample_input());
+    }
+
+    #[test]
+    fn it_reports_trees_of_the_wrong_size() {
+        assert_eq!(
+            parse_input("1 1 0 1 10\n").unwrap_err(),
+            ParseError::new(2, 1, "the rest of the tree", "")
+        );
+        assert_eq!(
+            parse_input("0 1 10 11").unwrap_err(),
+            ParseError::new(1, 8, "the end of the tree", "11")
+        );
+    }
+
+    #[test]
+    fn it_solves_part_one_correctly() {
+        assert_eq!(get_sample_input().sum_metadata(), 138);
+        assert_eq!(parse_input(REAL_INPUT).unwrap().sum_metadata(), 49426);
+    }
+
+    #[test]
+    fn it_solves_part_two_correctly() {
+        assert_eq!(get_sample_input().find_value(), 66);
+        assert_eq!(parse_input(REAL_INPUT).unwrap().find_value(), 40688);
+    }
+
+    fn get_sample_input() -> Node {
+        Node {
+            metadata: vec![1, 1, 2],
+            children: vec![
+                Node {
+                    metadata: vec![10, 11, 12],
+                    children: vec![],
+                },
+                Node {
+                    metadata: vec![2],
+                    children: vec![Node {
+                        metadata: vec![99],
+                        children: vec![],
+                    }],
+                },
+            ],
+        }
+    }
+
+    /// Trees whose metadata entries are all valid 1-based child indices or larger
+    fn trees() -> impl Strategy<Value = Node> {
+        let leaf = prop::collection::vec(1..10u32, 0..4).prop_map(|metadata| Node {
+            children: vec![],
+            metadata,
+        });
+        leaf.prop_recursive(4, 32, 4, |inner| {
+            (
+                prop::collection::vec(inner, 0..4),
+                prop::collection::vec(1..10u32, 1..4),
+            )
+                .prop_map(|(children, metadata)| Node { children, metadata })
+        })
+    }
+
+    fn to_data(node: &Node, data: &mut Vec<u32>) {
+        data.push(node.children.len() as u32);
+        data.push(node.metadata.len() as u32);
+        for child in node.children.iter() {
+            to_data(child, data);
+        }
+        data.extend(node.metadata.iter());
+    }
+
+    proptest! {
+        #[test]
+        fn it_parses_generated_trees(tree in trees()) {
+            let mut data = vec![];
+            to_data(&tree, &mut data);
+            let numbers: Vec<_> = data.iter().map(|n| n.to_string()).collect();
+            prop_assert_eq!(parse_input(&numbers.join(" ")).unwrap(), tree);
+            let truncated = numbers[..numbers.len() - 1].join(" ");
+            prop_assert!(parse_input(&truncated).is_err());
+        }
+    }
+}
@@ -1,10 +1,13 @@
+use bench::bench_solution;
 use criterion::{criterion_group, criterion_main, Criterion, Fun};
-use day_9::{solve_with_deque, solve_with_linked_list};
+use day_9::{solve_with_deque, solve_with_linked_list, Day9};
 
+const INPUT: &str = include_str!("../input");
 const PART_ONE_INPUT: (usize, u32) = (412, 71646);
 const PART_TWO_INPUT: (usize, u32) = (PART_ONE_INPUT.0, PART_ONE_INPUT.1 * 100);
 
 fn criterion_benchmark(c: &mut Criterion) {
+    bench_solution::<Day9>(c, 9, INPUT);
     let with_deque = Fun::new("with deque", |b, i| b.iter(|| solve_with_deque(i)));
     let with_linked_list = Fun::new("with linked list", |b, i| {
         b.iter(|| solve_with_linked_list(i))
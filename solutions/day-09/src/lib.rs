@@ -1,17 +1,41 @@
+use common::{end_of_input, lines, ParseResult, Solution};
 use std::collections::VecDeque;
 
 pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
 
-pub fn parse_input(input: &str) -> Result<(usize, u32)> {
-    let split_input: Vec<_> = input.trim().split_whitespace().collect();
-    let players = match split_input.get(0) {
-        Some(c) => c.parse()?,
-        None => return Err(Box::from("No player count found in input")),
-    };
-    let last_marble = match split_input.get(6) {
-        Some(c) => c.parse()?,
-        None => return Err(Box::from("No last marble value found in input")),
-    };
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = (usize, u32);
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        solve_with_deque(input)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        let &(player_count, last_marble) = input;
+        solve_with_deque(&(player_count, last_marble * 100))
+    }
+}
+
+/// Parses a line like `10 players; last marble is worth 1618 points`
+pub fn parse_input(input: &str) -> ParseResult<(usize, u32)> {
+    let line = lines(input)
+        .find(|line| !line.text.trim().is_empty())
+        .ok_or_else(|| end_of_input(input, "a player count"))?;
+    let mut cursor = line.cursor();
+    cursor.skip_whitespace();
+    let players = cursor.number("a player count")?;
+    cursor.literal(" players; last marble is worth ")?;
+    let last_marble = cursor.number("a marble value")?;
+    cursor.literal(" points")?;
+    cursor.end()?;
     Ok((players, last_marble))
 }
 
@@ -104,6 +128,7 @@ struct Marble {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     const SAMPLE_INPUTS: [&str; 6] = [
         "9 players; last marble is worth 25 points",
@@ -167,4 +192,17 @@ mod tests {
         assert_eq!(solve_with_deque(&PART_TWO_INPUT), PART_TWO_SOLUTION);
         assert_eq!(solve_with_linked_list(&PART_TWO_INPUT), PART_TWO_SOLUTION);
     }
+
+    proptest! {
+        #[test]
+        fn it_scores_the_same_with_both_implementations(
+            players in 1..30usize,
+            last_marble in 0..3000u32,
+        ) {
+            let input = format!("{} players; last marble is worth {} points", players, last_marble);
+            let game = parse_input(&input).unwrap();
+            prop_assert_eq!(game, (players, last_marble));
+            prop_assert_eq!(solve_with_deque(&game), solve_with_linked_list(&game));
+        }
+    }
 }
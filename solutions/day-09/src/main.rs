@@ -1,11 +1,10 @@
-use day_9::{parse_input, solve_with_deque as solve, Result};
+use day_9::Day9;
 
-const INPUT: &str = include_str!("../input");
+#[cfg(feature = "embedded-input")]
+const EMBEDDED_INPUT: Option<&str> = Some(include_str!("../input"));
+#[cfg(not(feature = "embedded-input"))]
+const EMBEDDED_INPUT: Option<&str> = None;
 
-fn main() -> Result<()> {
-    let parsed_input = parse_input(INPUT)?;
-    println!("{}", solve(&parsed_input));
-    let part_two_input = (parsed_input.0, parsed_input.1 * 100);
-    println!("{}", solve(&part_two_input));
-    Ok(())
+fn main() {
+    common::run::<Day9>(EMBEDDED_INPUT);
 }
@@ -0,0 +1,304 @@
+use animation::Simulation;
+use common::{end_of_input, lines, Cursor, ParseResult, Solution};
+use geometry::{BoundingBox, Point2};
+use std::fmt;
+
+const MAX_STEPS: u32 = 1_000_000;
+/// Larger skies are described instead of drawn
+const MAX_RENDER_SIZE: (u64, u64) = (200, 100);
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Point>;
+    type PartOne = String;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        solve(input).0
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        solve(input).1
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Point {
+    position: Point2,
+    velocity: Point2,
+}
+
+impl Point {
+    fn step(&mut self) {
+        self.position += self.velocity;
+    }
+
+    fn has_neighbor_in(&self, other_points: &[Point]) -> bool {
+        other_points
+            .iter()
+            .any(|p| self.position.chebyshev_distance(&p.position) == 1)
+    }
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<Point>> {
+    let points: Vec<_> = lines(input)
+        .filter(|line| !line.text.trim().is_empty())
+        .map(|line| {
+            let mut cursor = line.cursor();
+            cursor.literal("position=<")?;
+            let position = parse_vector(&mut cursor)?;
+            cursor.literal("> velocity=<")?;
+            let velocity = parse_vector(&mut cursor)?;
+            cursor.literal(">")?;
+            cursor.end()?;
+            Ok(Point { position, velocity })
+        })
+        .collect::<ParseResult<_>>()?;
+    if points.is_empty() {
+        return Err(end_of_input(input, "a point"));
+    }
+    Ok(points)
+}
+
+/// Parses a pair like ` 3, -2`, which may be padded with spaces
+fn parse_vector(cursor: &mut Cursor) -> ParseResult<Point2> {
+    cursor.skip_whitespace();
+    let x = cursor.number("an x value")?;
+    cursor.literal(",")?;
+    cursor.skip_whitespace();
+    let y = cursor.number("a y value")?;
+    Ok(Point2::new([x, y]))
+}
+
+/// The points moving across the sky, until each of them is next to another
+#[derive(Debug, Clone)]
+pub struct Sky {
+    points: Vec<Point>,
+    seconds: u32,
+}
+
+impl Sky {
+    pub fn new(points: Vec<Point>) -> Self {
+        Self { points, seconds: 0 }
+    }
+
+    fn tick(&mut self) {
+        self.points.iter_mut().for_each(|p| p.step());
+        self.seconds += 1;
+    }
+
+    fn is_aligned(&self) -> bool {
+        self.points.iter().all(|p| p.has_neighbor_in(&self.points))
+    }
+}
+
+impl Simulation for Sky {
+    fn step(&mut self) -> bool {
+        self.tick();
+        !self.is_aligned()
+    }
+}
+
+impl fmt::Display for Sky {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bounds = BoundingBox::from_points(self.points.iter().map(|p| p.position));
+        let (width, height) = bounds.map_or((0, 0), |b| (b.width(), b.height()));
+        if width > MAX_RENDER_SIZE.0 || height > MAX_RENDER_SIZE.1 {
+            writeln!(
+                f,
+                "After {} seconds the points are spread over {} by {}",
+                self.seconds, width, height
+            )
+        } else {
+            writeln!(f, "After {} seconds:", self.seconds)?;
+            write!(f, "{}", points_to_str(&self.points))
+        }
+    }
+}
+
+pub fn solve(points: &[Point]) -> (String, u32) {
+    let mut sky = Sky::new(points.to_vec());
+    while !sky.is_aligned() {
+        if sky.seconds == MAX_STEPS {
+            panic!("hit max steps");
+        }
+        sky.tick();
+    }
+    (points_to_str(&sky.points), sky.seconds)
+}
+
+fn points_to_str(points: &[Point]) -> String {
+    let bounds = BoundingBox::from_points(points.iter().map(|p| p.position)).unwrap();
+    let mut result = String::new();
+    for position in bounds.points() {
+        let c = if points.iter().any(|p| p.position == position) {
+            '#'
+        } else {
+            '.'
+        };
+        result.push(c);
+        if position.x() == bounds.max.x() {
+            result.push('\n');
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../sample-input");
+    const SAMPLE_OUTPUT: &str = include_str!("../sample-output");
+
+    #[test]
+    fn it_parses_input_correctly() {
+        assert_eq!(parse_input(SAMPLE_INPUT).unwrap(), get_sample_input());
+    }
+
+    #[test]
+    fn it_solves_part_one_correctly() {
+        assert_eq!(solve(&get_sample_input()).0, SAMPLE_OUTPUT);
+    }
+
+    #[test]
+    fn it_solves_part_two_correctly() {
+        assert_eq!(solve(&get_sample_input()).1, 3);
+    }
+
+    fn get_sample_input() -> [Point; 31] {
+        [
+            Point {
+                position: Point2::new([9, 1]),
+                velocity: Point2::new([0, 2]),
+            },
+            Point {
+                position: Point2::new([7, 0]),
+                velocity: Point2::new([-1, 0]),
+            },
+            Point {
+                position: Point2::new([3, -2]),
+                velocity: Point2::new([-1, 1]),
+            },
+            Point {
+                position: Point2::new([6, 10]),
+                velocity: Point2::new([-2, -1]),
+            },
+            Point {
+                position: Point2::new([2, -4]),
+                velocity: Point2::new([2, 2]),
+            },
+            Point {
+                position: Point2::new([-6, 10]),
+                velocity: Point2::new([2, -2]),
+            },
+            Point {
+                position: Point2::new([1, 8]),
+                velocity: Point2::new([1, -1]),
+            },
+            Point {
+                position: Point2::new([1, 7]),
+                velocity: Point2::new([1, 0]),
+            },
+            Point {
+                position: Point2::new([-3, 11]),
+                velocity: Point2::new([1, -2]),
+            },
+            Point {
+                position: Point2::new([7, 6]),
+                velocity: Point2::new([-1, -1]),
+            },
+            Point {
+                position: Point2::new([-2, 3]),
+                velocity: Point2::new([1, 0]),
+            },
+            Point {
+                position: Point2::new([-4, 3]),
+                velocity: Point2::new([2, 0]),
+            },
+            Point {
+                position: Point2::new([10, -3]),
+                velocity: Point2::new([-1, 1]),
+            },
+            Point {
+                position: Point2::new([5, 11]),
+                velocity: Point2::new([1, -2]),
+            },
+            Point {
+                position: Point2::new([4, 7]),
+                velocity: Point2::new([0, -1]),
+            },
+            Point {
+                position: Point2::new([8, -2]),
+                velocity: Point2::new([0, 1]),
+            },
+            Point {
+                position: Point2::new([15, 0]),
+                velocity: Point2::new([-2, 0]),
+            },
+            Point {
+                position: Point2::new([1, 6]),
+                velocity: Point2::new([1, 0]),
+            },
+            Point {
+                position: Point2::new([8, 9]),
+                velocity: Point2::new([0, -1]),
+            },
+            Point {
+                position: Point2::new([3, 3]),
+                velocity: Point2::new([-1, 1]),
+            },
+            Point {
+                position: Point2::new([0, 5]),
+                velocity: Point2::new([0, -1]),
+            },
+            Point {
+                position: Point2::new([-2, 2]),
+                velocity: Point2::new([2, 0]),
+            },
+            Point {
+                position: Point2::new([5, -2]),
+                velocity: Point2::new([1, 2]),
+            },
+            Point {
+                position: Point2::new([1, 4]),
+                velocity: Point2::new([2, 1]),
+            },
+            Point {
+                position: Point2::new([-2, 7]),
+                velocity: Point2::new([2, -2]),
+            },
+            Point {
+                position: Point2::new([3, 6]),
+                velocity: Point2::new([-1, -1]),
+            },
+            Point {
+                position: Point2::new([5, 0]),
+                velocity: Point2::new([1, 0]),
+            },
+            Point {
+                position: Point2::new([-6, 0]),
+                velocity: Point2::new([2, 0]),
+            },
+            Point {
+                position: Point2::new([5, 9]),
+                velocity: Point2::new([1, -2]),
+            },
+            Point {
+                position: Point2::new([14, 7]),
+                velocity: Point2::new([-2, 0]),
+            },
+            Point {
+                position: Point2::new([-3, 6]),
+                velocity: Point2::new([2, -1]),
+            },
+        ]
+    }
+}
@@ -0,0 +1,12 @@
+use bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_11::Day11;
+
+const INPUT: &str = include_str!("../input");
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_solution::<Day11>(c, 11, INPUT);
+}
+
+criterion_group! {name = benches; config = Criterion::default().sample_size(20); targets = criterion_benchmark}
+criterion_main!(benches);
@@ -0,0 +1,151 @@
+use common::{end_of_input, lines, Cursor, ParseResult, Solution};
+use cycle::find_cycle_within;
+
+pub const PART_ONE_GENERATIONS: usize = 20;
+pub const PART_TWO_GENERATIONS: usize = 50_000_000_000;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = (Vec<u32>, Vec<u8>);
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        let (initial_state, rules) = input;
+        solve(initial_state, rules, PART_ONE_GENERATIONS)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        let (initial_state, rules) = input;
+        solve(initial_state, rules, PART_TWO_GENERATIONS)
+    }
+}
+
+pub fn parse_input(input: &str) -> ParseResult<(Vec<u32>, Vec<u8>)> {
+    let mut lines = lines(input).filter(|line| !line.text.trim().is_empty());
+    let first_line = lines
+        .next()
+        .ok_or_else(|| end_of_input(input, "an initial state"))?;
+    let mut cursor = first_line.cursor();
+    cursor.literal("initial state: ")?;
+    let mut initial_state = vec![];
+    for i in 0.. {
+        if cursor.rest().trim_end().is_empty() {
+            break;
+        }
+        if parse_pot(&mut cursor)? == 1 {
+            initial_state.push(i);
+        }
+    }
+    let mut rules = vec![];
+    for line in lines {
+        let mut cursor = line.cursor();
+        let mut sequence = 0;
+        for _ in 0..5 {
+            sequence = sequence * 2 + parse_pot(&mut cursor)?;
+        }
+        cursor.literal(" => ")?;
+        if parse_pot(&mut cursor)? == 1 {
+            rules.push(sequence);
+        }
+        cursor.end()?;
+    }
+    Ok((initial_state, rules))
+}
+
+/// Reads a pot, 1 for `#` and 0 for `.`
+fn parse_pot(cursor: &mut Cursor) -> ParseResult<u8> {
+    let pot = cursor.char("a pot, # or .", |c| c == '#' || c == '.')?;
+    Ok(if pot == '#' { 1 } else { 0 })
+}
+
+fn next_generation(prev_state: &[i64], rules: &[u8]) -> Vec<i64> {
+    let mut new_state = vec![];
+    let first_filled_pot = prev_state[0];
+    let last_filled_pot = prev_state[prev_state.len() - 1];
+    for pot_number in (first_filled_pot - 2)..=(last_filled_pot + 2) {
+        let sequence = ((pot_number - 2)..=(pot_number + 2)).fold(0, |acc, i| {
+            if i >= first_filled_pot && i <= last_filled_pot && prev_state.binary_search(&i).is_ok()
+            {
+                return acc * 2 + 1;
+            }
+            acc * 2
+        });
+        if rules.binary_search(&sequence).is_ok() {
+            new_state.push(pot_number);
+        }
+    }
+    new_state
+}
+
+pub fn solve(initial_state: &[u32], rules: &[u8], generations: usize) -> i64 {
+    let initial_state: Vec<_> = initial_state.iter().map(|&x| i64::from(x)).collect();
+    let mut rules = rules.to_vec();
+    rules.sort();
+    // The pots settle into a pattern that keeps shifting along the row, so compare the patterns
+    // with the leftmost pot at 0
+    let cycle = find_cycle_within(
+        initial_state,
+        |state| next_generation(state, &rules),
+        |state| state.iter().map(|pot| pot - state[0]).collect::<Vec<_>>(),
+        generations,
+    );
+    match cycle {
+        Ok(cycle) => {
+            let state = cycle.state_at(generations);
+            let states = cycle.states();
+            let shift_per_cycle = states[cycle.start + cycle.length][0] - states[cycle.start][0];
+            let shift = shift_per_cycle * cycle.cycles_before(generations) as i64;
+            state.iter().map(|pot| pot + shift).sum()
+        }
+        Err(state) => state.iter().sum(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input");
+    const SAMPLE_INPUT: &str = include_str!("../sample-input");
+
+    #[test]
+    fn it_parses_input_correctly() {
+        let (parsed_initial_state, parsed_rules) = parse_input(SAMPLE_INPUT).unwrap();
+        let (sample_initial_state, sample_rules) = get_sample_input();
+        assert_eq!(parsed_initial_state, sample_initial_state);
+        assert_eq!(parsed_rules, sample_rules);
+    }
+
+    #[test]
+    fn it_solves_part_one_correctly() {
+        let (initial_state, rules) = get_sample_input();
+        assert_eq!(solve(&initial_state, &rules, PART_ONE_GENERATIONS), 325);
+    }
+
+    #[test]
+    fn it_solves_part_two_correctly() {
+        let (initial_state, rules) = parse_input(INPUT).unwrap();
+        assert_eq!(
+            solve(&initial_state, &rules, PART_TWO_GENERATIONS),
+            4_900_000_001_793
+        );
+    }
+
+    fn get_sample_input() -> (Vec<u32>, Vec<u8>) {
+        (
+            vec![0, 3, 5, 8, 9, 16, 17, 18, 22, 23, 24],
+            vec![
+                0b00011, 0b00100, 0b01000, 0b01010, 0b01011, 0b01100, 0b01111, 0b10101, 0b10111,
+                0b11010, 0b11011, 0b11100, 0b11101, 0b11110,
+            ],
+        )
+    }
+}
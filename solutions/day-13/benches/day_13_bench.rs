@@ -0,0 +1,12 @@
+use bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_13::Day13;
+
+const INPUT: &str = include_str!("../input");
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_solution::<Day13>(c, 13, INPUT);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,13 @@
+use bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_15::Day15;
+
+const INPUT: &str = include_str!("../input");
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_solution::<Day15>(c, 15, INPUT);
+}
+
+// A single run of part two takes several seconds so only take the minimum number of samples
+criterion_group! {name = benches; config = Criterion::default().sample_size(2); targets = criterion_benchmark}
+criterion_main!(benches);
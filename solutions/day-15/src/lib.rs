@@ -0,0 +1,451 @@
+use animation::Simulation;
+use common::{end_of_input, ParseError, ParseResult, Solution};
+use geometry::Point2;
+use grid::Grid;
+use log::{debug, trace};
+use pathfinding::bfs_distances;
+use std::convert::TryFrom;
+use std::fmt;
+
+const START_HP: u32 = 200;
+const START_ATTACK_POWER: u32 = 3;
+const PART_TWO_MIN_ATTACK_POWER: u32 = 4;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = World;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(World::from_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        let (rounds, _, remaining_hp) = input.clone().simulate_combat();
+        remaining_hp * rounds
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        let ((rounds, _, remaining_hp), _) =
+            input.clone().find_minimum_no_loss_elf_win_attack_power();
+        remaining_hp * rounds
+    }
+}
+
+// TODO:
+// EASY PART TWO IMPROVEMENT: End combat when first elf dies
+// Opportunity for big clean up: Find a way to actually remove dead units
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Team {
+    Goblin,
+    Elf,
+}
+
+impl Team {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'G' => Some(Team::Goblin),
+            'E' => Some(Team::Elf),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Team::Goblin => 'G',
+            Team::Elf => 'E',
+        }
+    }
+
+    pub fn full_name(&self) -> String {
+        match &self {
+            Team::Goblin => String::from("Goblins"),
+            Team::Elf => String::from("Elves"),
+        }
+    }
+}
+
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Unit {
+    id: usize,
+    team: Team,
+    hit_points: u32,
+    attack_power: u32,
+    position: Point2,
+}
+
+impl Unit {
+    fn new(id: usize, team: Team, position: Point2) -> Self {
+        Self {
+            id,
+            team,
+            hit_points: START_HP,
+            attack_power: START_ATTACK_POWER,
+            position,
+        }
+    }
+
+    fn step(&mut self, world: &mut World) -> bool {
+        if self.hit_points == 0 {
+            return false;
+        }
+        let living_enemy_count = world
+            .units
+            .iter()
+            .filter(|u| u.team != self.team && u.hit_points > 0)
+            .count();
+        if living_enemy_count == 0 {
+            return true;
+        }
+        let mut adjacent_live_enemies: Vec<_> = self
+            .position
+            .orthogonal_neighbors()
+            .iter()
+            .filter_map(|p| world.get_live_unit_at_position(*p))
+            .filter(|u| u.team != self.team && u.hit_points > 0)
+            .collect();
+        if adjacent_live_enemies.is_empty() {
+            self.move_(world);
+            adjacent_live_enemies = self
+                .position
+                .orthogonal_neighbors()
+                .iter()
+                .filter_map(|p| world.get_live_unit_at_position(*p))
+                .filter(|u| u.team != self.team && u.hit_points > 0)
+                .collect();
+        }
+        if adjacent_live_enemies.is_empty() {
+            return false;
+        }
+        let target_id = adjacent_live_enemies
+            .iter()
+            .min_by_key(|e| (e.hit_points, e.position))
+            .map(|t| t.id)
+            .unwrap();
+        let target = world.get_unit_mut(target_id).unwrap();
+        target.hit_points = target.hit_points.saturating_sub(self.attack_power);
+        false
+    }
+
+    fn move_(&mut self, world: &World) {
+        let distances = bfs_distances(self.position, |&p| world.get_open_neighbors(p));
+        let target = world
+            .units
+            .iter()
+            .filter(|u| u.team != self.team && u.hit_points > 0)
+            .flat_map(|u| world.get_open_neighbors(u.position))
+            .filter_map(|p| distances.get(&p).map(|&distance| (distance, p)))
+            .min();
+        let (distance, target) = match target {
+            Some(target) => target,
+            None => return,
+        };
+        // Step towards the target along the first shortest path in reading order
+        let distances_to_target = bfs_distances(target, |&p| world.get_open_neighbors(p));
+        self.position = world
+            .get_open_neighbors(self.position)
+            .into_iter()
+            .filter(|p| distances_to_target.get(p) == Some(&(distance - 1)))
+            .min()
+            .unwrap();
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({})", self.team, self.hit_points)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct World {
+    walls: Grid<bool>,
+    units: Vec<Unit>,
+}
+
+impl World {
+    pub fn from_input(input: &str) -> ParseResult<Self> {
+        let mut units = vec![];
+        let walls = Grid::try_from_char_map(input.trim_end(), |(x, y), c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => {
+                let team = Team::from_char(c)
+                    .ok_or_else(|| ParseError::at_cell((x, y), "a wall, floor or unit", c))?;
+                units.push(Unit::new(units.len(), team, Point2::from((x, y))));
+                Ok(false)
+            }
+        })?;
+        if units.is_empty() {
+            return Err(end_of_input(input, "a unit"));
+        }
+        Ok(Self { walls, units })
+    }
+
+    /// Plays a round, returning the winning team and its remaining hit points if combat ended
+    fn round(&mut self) -> Option<(Team, u32)> {
+        self.units.sort_by_key(|u| u.position);
+        for i in 0..self.units.len() {
+            let mut unit = self.units[i].clone();
+            let game_over = unit.step(self);
+            self.units[i] = unit;
+            if game_over {
+                self.units.retain(|u| u.hit_points > 0);
+                let winning_team = self.units[0].team;
+                let remaining_hp: u32 = self.units.iter().map(|u| u.hit_points).sum();
+                return Some((winning_team, remaining_hp));
+            }
+        }
+        self.units.retain(|u| u.hit_points > 0);
+        None
+    }
+
+    pub fn simulate_combat(&mut self) -> (u32, Team, u32) {
+        let mut rounds = 0;
+        let mut result = None;
+        while result.is_none() {
+            trace!("After {} rounds:\n{}", rounds, self);
+            result = self.round();
+            rounds += 1;
+        }
+        let (winning_team, remaining_hp) = result.unwrap();
+        (rounds - 1, winning_team, remaining_hp)
+    }
+
+    fn has_wall_at_position(&self, position: Point2) -> bool {
+        // Everything outside of the map is treated as a wall
+        grid::Position::try_from(position)
+            .ok()
+            .and_then(|p| self.walls.get(p).cloned())
+            .unwrap_or(true)
+    }
+
+    fn get_live_unit_at_position(&self, position: Point2) -> Option<&Unit> {
+        self.units
+            .iter()
+            .find(|u| u.hit_points > 0 && u.position == position)
+    }
+
+    fn get_unit_mut(&mut self, id: usize) -> Option<&mut Unit> {
+        self.units.iter_mut().find(|u| u.id == id)
+    }
+
+    fn get_open_neighbors(&self, point: Point2) -> Vec<Point2> {
+        point
+            .orthogonal_neighbors()
+            .into_iter()
+            .filter(|&p| {
+                !self.has_wall_at_position(p) && self.get_live_unit_at_position(p).is_none()
+            })
+            .collect()
+    }
+
+    fn set_elves_attack_power(&mut self, attack_power: u32) {
+        for elf in self.units.iter_mut().filter(|u| u.team == Team::Elf) {
+            elf.attack_power = attack_power;
+        }
+    }
+
+    pub fn find_minimum_no_loss_elf_win_attack_power(&mut self) -> ((u32, Team, u32), u32) {
+        for attack_power in PART_TWO_MIN_ATTACK_POWER.. {
+            let mut world = self.clone();
+            debug!("Trying attack power {}", attack_power);
+            world.set_elves_attack_power(attack_power);
+            let before_combat_elf_count =
+                world.units.iter().filter(|u| u.team == Team::Elf).count();
+            let result = world.simulate_combat();
+            let after_combat_elf_count = world.units.iter().filter(|u| u.team == Team::Elf).count();
+            if result.1 == Team::Elf && before_combat_elf_count == after_combat_elf_count {
+                return (result, attack_power);
+            }
+        }
+        unreachable!()
+    }
+}
+
+impl Simulation for World {
+    fn step(&mut self) -> bool {
+        self.round().is_none()
+    }
+}
+
+impl fmt::Display for World {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (max_x, max_y) = (self.walls.width(), self.walls.height());
+        for i in 0..max_y {
+            let mut row_units = vec![];
+            for j in 0..max_x {
+                let current_position = Point2::from((j, i));
+                let has_wall = self.has_wall_at_position(current_position);
+                let unit = self.get_live_unit_at_position(current_position);
+                if let Some(u) = unit {
+                    row_units.push(u);
+                    write!(f, "{}", u.team)?;
+                } else if has_wall {
+                    write!(f, "#")?;
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+            write!(f, " ")?;
+            for u in row_units.iter() {
+                write!(f, "{} ", u)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MOVEMENT_SAMPLE_INPUT: &str = include_str!("../sample-input-movement");
+    const COMBAT_SAMPLE_INPUT: &str = include_str!("../sample-input-combat");
+    const SAMPLE_INPUTS: [&str; 5] = [
+        include_str!("../sample-input-1"),
+        include_str!("../sample-input-2"),
+        include_str!("../sample-input-3"),
+        include_str!("../sample-input-4"),
+        include_str!("../sample-input-5"),
+    ];
+    const PART_ONE_SAMPLE_RESULTS: [(u32, Team, u32); 5] = [
+        (37, Team::Elf, 982),
+        (46, Team::Elf, 859),
+        (35, Team::Goblin, 793),
+        (54, Team::Goblin, 536),
+        (20, Team::Goblin, 937),
+    ];
+    const PART_TWO_SAMPLE_RESULTS: [u32; 4] = [4, 15, 12, 34];
+    const SNAPSHOT_ROUNDS: [u32; 3] = [1, 2, 3];
+
+    /// The world before combat, after each of `SNAPSHOT_ROUNDS` and when combat ends
+    fn render_combat(input: &str) -> String {
+        let mut world = World::from_input(input).unwrap();
+        let mut rendered = format!("Initial state:\n{}", world);
+        for round in 1.. {
+            let result = world.round();
+            if let Some((winning_team, remaining_hp)) = result {
+                rendered += &format!(
+                    "\nCombat ends during round {}, {} win with {} total hit points left:\n{}",
+                    round,
+                    winning_team.full_name(),
+                    remaining_hp,
+                    world
+                );
+                return rendered;
+            }
+            if SNAPSHOT_ROUNDS.contains(&round) {
+                rendered += &format!(
+                    "\nAfter {} round{}:\n{}",
+                    round,
+                    if round > 1 { "s" } else { "" },
+                    world
+                );
+            }
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn it_rejects_maps_without_units() {
+        assert_eq!(
+            World::from_input("#####\n#...#\n#####\n").unwrap_err(),
+            ParseError::new(4, 1, "a unit", "")
+        );
+    }
+
+    #[test]
+    fn it_solves_combat_sample_correctly() {
+        let (rounds, winning_team, remaining_hp) = World::from_input(COMBAT_SAMPLE_INPUT)
+            .unwrap()
+            .simulate_combat();
+        assert_eq!(rounds, 47);
+        assert_eq!(winning_team, Team::Goblin);
+        assert_eq!(remaining_hp, 590);
+    }
+
+    #[test]
+    fn it_solves_movement_sample_correctly() {
+        let (rounds, winning_team, remaining_hp) = World::from_input(MOVEMENT_SAMPLE_INPUT)
+            .unwrap()
+            .simulate_combat();
+        assert_eq!(rounds, 18);
+        assert_eq!(winning_team, Team::Goblin);
+        assert_eq!(remaining_hp, 1546);
+    }
+
+    #[test]
+    fn it_solves_samples_correctly() {
+        for sample_number in 0..5 {
+            let input = SAMPLE_INPUTS[sample_number];
+            let (rounds, winning_team, remaining_hp) =
+                World::from_input(input).unwrap().simulate_combat();
+            let expected = PART_ONE_SAMPLE_RESULTS[sample_number];
+            assert_eq!(
+                rounds, expected.0,
+                "Wrong rounds for sample #{}",
+                sample_number
+            );
+            assert_eq!(
+                winning_team, expected.1,
+                "Wrong team for sample #{}",
+                sample_number
+            );
+            assert_eq!(
+                remaining_hp, expected.2,
+                "Wrong remaining hp for sample #{}",
+                sample_number
+            );
+        }
+    }
+
+    #[test]
+    fn it_solves_part_two_samples_correctly() {
+        let input = COMBAT_SAMPLE_INPUT;
+        let result = World::from_input(input)
+            .unwrap()
+            .find_minimum_no_loss_elf_win_attack_power();
+        assert_eq!(result.1, 15, "failed for combat sample");
+
+        for sample_number in 1..4 {
+            let input = SAMPLE_INPUTS[sample_number];
+            let result = World::from_input(input)
+                .unwrap()
+                .find_minimum_no_loss_elf_win_attack_power();
+            let expected = PART_TWO_SAMPLE_RESULTS[sample_number - 1];
+            assert_eq!(result.1, expected, "failed for sample #{}", sample_number);
+        }
+    }
+
+    #[test]
+    fn it_renders_combat() {
+        let samples = [
+            ("sample-input-movement", MOVEMENT_SAMPLE_INPUT),
+            ("sample-input-combat", COMBAT_SAMPLE_INPUT),
+        ];
+        let numbered_samples = SAMPLE_INPUTS
+            .iter()
+            .enumerate()
+            .map(|(i, input)| (format!("sample-input-{}", i + 1), *input));
+        let samples = samples
+            .iter()
+            .map(|&(name, input)| (String::from(name), input))
+            .chain(numbered_samples);
+        for (name, input) in samples {
+            common::assert_snapshot(env!("CARGO_MANIFEST_DIR"), &name, &render_combat(input));
+        }
+    }
+}
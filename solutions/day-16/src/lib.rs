@@ -0,0 +1,595 @@
+use common::{end_of_input, section_lines, Line, ParseError, ParseResult, Solution};
+use elfcode::{Op, Program};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+pub struct Day16;
+
+const REGISTERS: usize = 4;
+
+impl Solution for Day16 {
+    type Input = Device;
+    type PartOne = usize;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Device::from_input(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        solve_part_one(&input.samples)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        input.run_program()
+    }
+}
+
+/// The samples of the manual and the test program with its op numbers decoded from them
+#[derive(Debug, Clone)]
+pub struct Device {
+    pub samples: Vec<Sample>,
+    /// The op of each op number in the samples
+    pub op_by_number: HashMap<u32, Op>,
+    pub program: Program<u32, REGISTERS>,
+}
+
+impl Device {
+    /// Parses the samples and the test program and decodes the program's op numbers, which may
+    /// use any mapping the samples allow as long as they all give the program the same result
+    ///
+    /// The program is run once for each mapping, so samples that allow more than
+    /// `MAPPING_LIMIT` mappings are reported as ambiguous instead of checking what can be
+    /// billions of them.
+    pub fn from_input(input: &str) -> Result<Self> {
+        let (samples, program_input) = parse_input(input)?;
+        let mut decoded: Option<(Self, u32)> = None;
+        let mut mapping_count = 0;
+        let mut check_mapping = |op_by_number: &HashMap<u32, Op>| -> Result<()> {
+            mapping_count += 1;
+            if mapping_count > MAPPING_LIMIT {
+                let limit = MAPPING_LIMIT;
+                return Err(Box::new(DecodeError::TooManyMappings { limit }));
+            }
+            let program_lines = section_lines(input, program_input);
+            let program = Program::parse_numeric(program_lines, |number| {
+                op_by_number.get(&(number as u32)).cloned()
+            })?;
+            let device = Self {
+                samples: vec![],
+                op_by_number: op_by_number.clone(),
+                program,
+            };
+            let result = device.run_program();
+            match &decoded {
+                None => decoded = Some((device, result)),
+                Some((_, first_result)) if *first_result != result => {
+                    let results = [*first_result, result];
+                    return Err(Box::new(DecodeError::DifferentResults { results }));
+                }
+                Some(_) => {}
+            }
+            Ok(())
+        };
+        let mut error = None;
+        decode_ops(&samples, |op_by_number| match check_mapping(op_by_number) {
+            Ok(()) => true,
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        })?;
+        if let Some(error) = error {
+            return Err(error);
+        }
+        // Decoding gives at least one mapping
+        let (device, _) = decoded.unwrap();
+        Ok(Self { samples, ..device })
+    }
+
+    /// Runs the test program, returning the value left in register 0
+    pub fn run_program(&self) -> u32 {
+        let mut program = self.program.clone();
+        program.run();
+        program.registers[0]
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Sample {
+    before: [u32; 4],
+    after: [u32; 4],
+    instruction: [u32; 4],
+}
+
+impl Sample {
+    /// Parses a sample from its lines, like
+    ///
+    /// ```text
+    /// Before: [3, 2, 1, 1]
+    /// 9 2 1 2
+    /// After:  [3, 2, 2, 1]
+    /// ```
+    fn from_lines(lines: &[Line]) -> ParseResult<Self> {
+        fn parse_registers(line: &Line, prefix: &str) -> ParseResult<[u32; 4]> {
+            let mut cursor = line.cursor();
+            cursor.literal(prefix)?;
+            let mut result = [0; 4];
+            for (i, register) in result.iter_mut().enumerate() {
+                if i > 0 {
+                    cursor.literal(", ")?;
+                }
+                *register = cursor.number("a register value")?;
+            }
+            cursor.literal("]")?;
+            cursor.end()?;
+            Ok(result)
+        }
+        let line = |i: usize, expected: &str| match lines.get(i) {
+            Some(line) => Ok(line),
+            None => {
+                let number = lines.last().map_or(1, |line| line.number + 1);
+                Err(ParseError::new(number, 1, expected, ""))
+            }
+        };
+        let sample = Sample {
+            before: parse_registers(line(0, "registers before")?, "Before: [")?,
+            instruction: parse_instruction(line(1, "an instruction")?)?,
+            after: parse_registers(line(2, "registers after")?, "After:  [")?,
+        };
+        if let Some(extra) = lines.get(3) {
+            return Err(extra.mismatch("a blank line after the sample"));
+        }
+        Ok(sample)
+    }
+
+    fn behaves_like(&self, op: Op) -> bool {
+        let [_, a, b, c] = self.instruction;
+        let names_missing_register = op
+            .register_operands()
+            .iter()
+            .zip([a, b, c].iter())
+            .any(|(&is_register, &operand)| is_register && operand as usize >= REGISTERS);
+        if names_missing_register {
+            return false;
+        }
+        let mut registers = self.before;
+        op.execute(a, b, c, &mut registers);
+        registers == self.after
+    }
+
+    fn find_possible_ops(&self) -> Vec<Op> {
+        Op::ALL
+            .iter()
+            .cloned()
+            .filter(|&op| self.behaves_like(op))
+            .collect()
+    }
+
+    fn get_op_number(&self) -> u32 {
+        self.instruction[0]
+    }
+}
+
+/// Parses an instruction like `9 2 1 2`, whose last operand is always a register
+fn parse_instruction(line: &Line) -> ParseResult<[u32; 4]> {
+    let mut cursor = line.cursor();
+    let mut result = [0; 4];
+    for (i, value) in result.iter_mut().enumerate() {
+        if i > 0 {
+            cursor.literal(" ")?;
+        }
+        let start = cursor;
+        *value = cursor.number("an op number or operand")?;
+        if i == 3 && *value as usize >= REGISTERS {
+            return Err(start.error(format!("a register below {}", REGISTERS)));
+        }
+    }
+    cursor.end()?;
+    Ok(result)
+}
+
+/// Parses the samples and the test program after them, checking the program's instructions
+/// even though their ops aren't known yet
+pub fn parse_input(input: &str) -> ParseResult<(Vec<Sample>, &str)> {
+    let mut parts = input.splitn(2, "\n\n\n");
+    let samples = parts
+        .next()
+        .unwrap_or_default()
+        .split("\n\n")
+        .map(|section| Sample::from_lines(&section_lines(input, section).collect::<Vec<_>>()))
+        .collect::<ParseResult<_>>()?;
+    let program = parts
+        .next()
+        .ok_or_else(|| end_of_input(input, "a test program after the samples"))?;
+    for line in section_lines(input, program).filter(|line| !line.text.is_empty()) {
+        parse_instruction(&line)?;
+    }
+    Ok((samples, program))
+}
+
+pub fn solve_part_one(samples: &[Sample]) -> usize {
+    samples
+        .iter()
+        .map(|s| s.find_possible_ops())
+        .filter(|ops| ops.len() >= 3)
+        .count()
+}
+
+/// Most op mappings `Device::from_input` runs the test program with before it reports the
+/// samples as ambiguous
+pub const MAPPING_LIMIT: usize = 100;
+
+/// Why the samples don't give a usable mapping from op numbers to ops
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// No op behaves like all of these samples, which share an op number
+    Contradiction { number: u32, samples: Vec<usize> },
+    /// The samples for these op numbers leave fewer ops than there are numbers
+    NotEnoughOps {
+        numbers: Vec<u32>,
+        samples: Vec<usize>,
+    },
+    /// The samples allow more mappings than the limit on how many are checked
+    TooManyMappings { limit: usize },
+    /// The samples allow mappings that give the test program these different results
+    DifferentResults { results: [u32; 2] },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |samples: &[usize]| {
+            let samples: Vec<_> = samples.iter().map(|i| (i + 1).to_string()).collect();
+            samples.join(", ")
+        };
+        match self {
+            DecodeError::Contradiction { number, samples } => write!(
+                f,
+                "No op behaves like samples {} for op number {}",
+                join(samples),
+                number
+            ),
+            DecodeError::NotEnoughOps { numbers, samples } => {
+                let numbers: Vec<_> = numbers.iter().map(|n| n.to_string()).collect();
+                write!(
+                    f,
+                    "Samples {} leave fewer ops than op numbers {}",
+                    join(samples),
+                    numbers.join(", ")
+                )
+            }
+            DecodeError::TooManyMappings { limit } => {
+                write!(f, "The samples allow more than {} op mappings", limit)
+            }
+            DecodeError::DifferentResults { results } => write!(
+                f,
+                "The samples allow op mappings that give the test program different results, \
+                 {} and {}",
+                results[0], results[1]
+            ),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// The ops that behave like every one of the samples
+fn common_ops(samples: &[Sample], indices: &[usize]) -> BTreeSet<Op> {
+    indices
+        .iter()
+        .fold(Op::ALL.iter().cloned().collect(), |ops, &i| {
+            let possible: BTreeSet<_> = samples[i].find_possible_ops().into_iter().collect();
+            ops.intersection(&possible).cloned().collect()
+        })
+}
+
+/// Calls `on_mapping` with every mapping from the op numbers in the samples to different ops
+/// that agrees with all of them, numbers without samples are left out
+///
+/// The mappings are found one at a time, and the search stops as soon as `on_mapping` returns
+/// false, since partial samples can allow billions of them.
+pub fn decode_ops<F>(
+    samples: &[Sample],
+    mut on_mapping: F,
+) -> ::std::result::Result<(), DecodeError>
+where
+    F: FnMut(&HashMap<u32, Op>) -> bool,
+{
+    let mut samples_by_number: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+    for (i, sample) in samples.iter().enumerate() {
+        samples_by_number
+            .entry(sample.get_op_number())
+            .or_default()
+            .push(i);
+    }
+    let mut candidates = vec![];
+    for (&number, indices) in samples_by_number.iter() {
+        let ops = common_ops(samples, indices);
+        if ops.is_empty() {
+            // Keep only the samples needed to rule out every op
+            let mut conflicting = indices.clone();
+            for i in indices.iter() {
+                let without: Vec<_> = conflicting.iter().cloned().filter(|j| j != i).collect();
+                if common_ops(samples, &without).is_empty() {
+                    conflicting = without;
+                }
+            }
+            return Err(DecodeError::Contradiction {
+                number,
+                samples: conflicting,
+            });
+        }
+        candidates.push((number, ops));
+    }
+    // Numbers with the fewest candidates first to prune the search early
+    candidates.sort_by_key(|(number, ops)| (ops.len(), *number));
+    let mut found = false;
+    assign_ops(&candidates, &mut HashMap::new(), &mut |mapping| {
+        found = true;
+        on_mapping(mapping)
+    });
+    if !found {
+        let numbers = find_overconstrained_numbers(&candidates);
+        let samples = numbers
+            .iter()
+            .flat_map(|n| samples_by_number[n].iter().cloned())
+            .collect::<BTreeSet<_>>();
+        return Err(DecodeError::NotEnoughOps {
+            numbers,
+            samples: samples.into_iter().collect(),
+        });
+    }
+    Ok(())
+}
+
+/// Backtracks through the candidates, giving each number an op no other number has, and
+/// passes each complete mapping to `on_mapping`, returning false once it asked to stop
+fn assign_ops<F>(
+    candidates: &[(u32, BTreeSet<Op>)],
+    mapping: &mut HashMap<u32, Op>,
+    on_mapping: &mut F,
+) -> bool
+where
+    F: FnMut(&HashMap<u32, Op>) -> bool,
+{
+    let (number, ops) = match candidates.first() {
+        Some(candidate) => candidate,
+        None => return on_mapping(mapping),
+    };
+    for &op in ops.iter() {
+        if mapping.values().all(|&used| used != op) {
+            mapping.insert(*number, op);
+            let keep_going = assign_ops(&candidates[1..], mapping, on_mapping);
+            mapping.remove(number);
+            if !keep_going {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// The smallest set of numbers whose candidates have fewer ops than there are numbers, which
+/// exists whenever no mapping does
+fn find_overconstrained_numbers(candidates: &[(u32, BTreeSet<Op>)]) -> Vec<u32> {
+    let mut numbers: Vec<_> = candidates.iter().map(|(number, _)| *number).collect();
+    numbers.sort_unstable();
+    if candidates.len() > Op::ALL.len() {
+        return numbers;
+    }
+    let mut subsets: Vec<u32> = (1..1 << candidates.len()).collect();
+    subsets.sort_by_key(|subset| subset.count_ones());
+    subsets
+        .into_iter()
+        .find_map(|subset| {
+            let chosen: Vec<_> = (0..candidates.len())
+                .filter(|i| subset & (1 << i) != 0)
+                .collect();
+            let ops: BTreeSet<_> = chosen
+                .iter()
+                .flat_map(|&i| candidates[i].1.iter().cloned())
+                .collect();
+            if ops.len() < chosen.len() {
+                let mut numbers: Vec<_> = chosen.iter().map(|&i| candidates[i].0).collect();
+                numbers.sort_unstable();
+                Some(numbers)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(numbers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::lines;
+
+    const SAMPLE_SAMPLE_INPUT: &str = "Before: [3, 2, 1, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]";
+    const SAMPLE_SAMPLE: Sample = Sample {
+        before: [3, 2, 1, 1],
+        instruction: [9, 2, 1, 2],
+        after: [3, 2, 2, 1],
+    };
+
+    #[test]
+    fn it_parses_samples_correctly() {
+        let sample_lines: Vec<_> = lines(SAMPLE_SAMPLE_INPUT).collect();
+        assert_eq!(Sample::from_lines(&sample_lines), Ok(SAMPLE_SAMPLE));
+        assert_eq!(
+            Sample::from_lines(&sample_lines[..2]),
+            Err(ParseError::new(3, 1, "registers after", ""))
+        );
+    }
+
+    #[test]
+    fn it_reports_where_the_input_is_malformed() {
+        let input = format!("{}\n\nBefore: [3, 2, 1]\n9 2 1 2\n", SAMPLE_SAMPLE_INPUT);
+        assert_eq!(
+            parse_input(&input).unwrap_err().to_string(),
+            "line 5, column 17: expected \", \", found \"]\""
+        );
+        let input = format!("{}\n\n\n\n9 2 1 2\n9 2 x 2\n", SAMPLE_SAMPLE_INPUT);
+        assert_eq!(
+            parse_input(&input).unwrap_err(),
+            ParseError::new(8, 5, "an op number or operand", "x")
+        );
+    }
+
+    #[test]
+    fn it_rules_out_ops_reading_missing_registers() {
+        let sample = Sample {
+            before: [0, 0, 0, 0],
+            instruction: [9, 9, 0, 0],
+            after: [9, 0, 0, 0],
+        };
+        assert_eq!(sample.find_possible_ops(), vec![Op::Seti]);
+        let input = "Before: [0, 0, 0, 0]\n9 0 0 7\nAfter:  [0, 0, 0, 0]\n\n\n\n9 0 0 0\n";
+        assert_eq!(
+            parse_input(input).unwrap_err().to_string(),
+            "line 2, column 7: expected a register below 4, found \"7\""
+        );
+    }
+
+    #[test]
+    fn it_finds_correct_possible_ops() {
+        let mut result = SAMPLE_SAMPLE.find_possible_ops();
+        let mut expected = [Op::Mulr, Op::Addi, Op::Seti];
+        // sort so order doesn't influence the equality
+        result.sort();
+        expected.sort();
+        assert_eq!(result, expected);
+    }
+
+    // Only behaves like addr
+    const ADDR_SAMPLE: Sample = Sample {
+        before: [0, 5, 3, 0],
+        instruction: [4, 1, 2, 3],
+        after: [0, 5, 3, 8],
+    };
+
+    fn with_number(sample: &Sample, number: u32) -> Sample {
+        let mut sample = sample.clone();
+        sample.instruction[0] = number;
+        sample
+    }
+
+    fn decode_all(samples: &[Sample]) -> ::std::result::Result<Vec<HashMap<u32, Op>>, DecodeError> {
+        let mut mappings = vec![];
+        decode_ops(samples, |mapping| {
+            mappings.push(mapping.clone());
+            true
+        })?;
+        Ok(mappings)
+    }
+
+    #[test]
+    fn it_decodes_the_only_mapping() {
+        let (samples, _) = parse_input(include_str!("../input")).unwrap();
+        let mappings = decode_all(&samples).unwrap();
+        assert_eq!(mappings.len(), 1);
+        assert_eq!(mappings[0].len(), 16);
+    }
+
+    #[test]
+    fn it_decodes_every_mapping_of_partial_samples() {
+        let mappings = decode_all(&[SAMPLE_SAMPLE, ADDR_SAMPLE]).unwrap();
+        let mut ops: Vec<_> = mappings.iter().map(|m| (m[&9], m[&4])).collect();
+        ops.sort();
+        assert_eq!(
+            ops,
+            vec![
+                (Op::Addi, Op::Addr),
+                (Op::Mulr, Op::Addr),
+                (Op::Seti, Op::Addr)
+            ]
+        );
+        assert_eq!(decode_all(&[]).unwrap(), vec![HashMap::new()]);
+    }
+
+    #[test]
+    fn it_stops_decoding_samples_that_allow_too_many_mappings() {
+        // Thirteen ops leave every register at zero, so nine numbers allow 13!/4! mappings
+        let mut input = String::new();
+        for number in 0..9 {
+            input += &format!(
+                "Before: [0, 0, 0, 0]\n{} 0 0 0\nAfter:  [0, 0, 0, 0]\n\n",
+                number
+            );
+        }
+        let (samples, _) = parse_input(&format!("{}\n\n0 0 0 0\n", input)).unwrap();
+        let mut mapping_count = 0;
+        decode_ops(&samples, |_| {
+            mapping_count += 1;
+            mapping_count < 3
+        })
+        .unwrap();
+        assert_eq!(mapping_count, 3);
+        assert_eq!(
+            Device::from_input(&format!("{}\n\n0 0 0 0\n", input))
+                .unwrap_err()
+                .to_string(),
+            "The samples allow more than 100 op mappings"
+        );
+    }
+
+    #[test]
+    fn it_decodes_the_test_program_while_parsing() {
+        let program_input = |program: &str| format!("{}\n\n\n\n{}\n", SAMPLE_SAMPLE_INPUT, program);
+        // Addi, mulr and seti all leave every register at zero
+        let device = Device::from_input(&program_input("9 0 0 1")).unwrap();
+        assert_eq!(device.samples, vec![SAMPLE_SAMPLE]);
+        assert_eq!(device.run_program(), 0);
+        let error = Device::from_input(&program_input("9 2 1 0")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The samples allow op mappings that give the test program different results, 1 and 0"
+        );
+        let error = Device::from_input(&program_input("9 2 1 0\n4 0 0 0")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 8, column 1: expected a known op number, found \"4\""
+        );
+        let input = format!(
+            "{}\n\nBefore: [0, 5, 3, 0]\n9 1 2 3\nAfter:  [0, 5, 3, 8]\n\n\n\n9 0 0 0\n",
+            SAMPLE_SAMPLE_INPUT
+        );
+        assert_eq!(
+            Device::from_input(&input).unwrap_err().to_string(),
+            "No op behaves like samples 1, 2 for op number 9"
+        );
+    }
+
+    #[test]
+    fn it_reports_conflicting_samples() {
+        let samples = [
+            SAMPLE_SAMPLE,
+            with_number(&SAMPLE_SAMPLE, 4),
+            ADDR_SAMPLE,
+            ADDR_SAMPLE,
+        ];
+        let error = decode_all(&samples).unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError::Contradiction {
+                number: 4,
+                samples: vec![1, 3],
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "No op behaves like samples 2, 4 for op number 4"
+        );
+        let samples = [SAMPLE_SAMPLE, ADDR_SAMPLE, with_number(&ADDR_SAMPLE, 7)];
+        assert_eq!(
+            decode_all(&samples).unwrap_err(),
+            DecodeError::NotEnoughOps {
+                numbers: vec![4, 7],
+                samples: vec![1, 2],
+            }
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use animation::Simulation;
+use common::{end_of_input, lines, Line, ParseResult, Solution};
+use grid::{Bounds, Position, SparseGrid};
+use log::trace;
+use raster::{Color, Draw, Image};
+use std::fmt;
+use std::mem;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = World;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(World::from_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        let mut world = input.clone();
+        world.simulate_water();
+        world.count_water_and_flow()
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        let mut world = input.clone();
+        world.simulate_water();
+        world.count_water()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Tile {
+    Clay,
+    Water,
+    Flow,
+    Spring,
+}
+
+impl Tile {
+    fn to_char(&self) -> char {
+        match self {
+            Tile::Clay => '#',
+            Tile::Water => '~',
+            Tile::Spring => '+',
+            Tile::Flow => '|',
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Tile::Clay => [140, 80, 30],
+            Tile::Water => [30, 80, 200],
+            Tile::Spring => [220, 40, 40],
+            Tile::Flow => [120, 180, 240],
+        }
+    }
+}
+
+const SAND_COLOR: Color = [240, 225, 180];
+
+/// Parses a vein of clay like `x=495, y=2..7` or `y=7, x=495..501`
+fn parse_vein(line: Line) -> ParseResult<impl Iterator<Item = Position>> {
+    let mut cursor = line.cursor();
+    let axis = cursor.char("x or y", |c| c == 'x' || c == 'y')?;
+    cursor.literal("=")?;
+    let value: usize = cursor.number("a coordinate")?;
+    let other_axis = if axis == 'x' { 'y' } else { 'x' };
+    cursor.literal(", ")?;
+    cursor.char(&other_axis.to_string(), |c| c == other_axis)?;
+    cursor.literal("=")?;
+    let range_start: usize = cursor.number("the start of a range")?;
+    cursor.literal("..")?;
+    let range_end: usize = cursor.number("the end of a range")?;
+    cursor.end()?;
+    Ok((range_start..=range_end).map(move |other| {
+        if axis == 'x' {
+            (value, other)
+        } else {
+            (other, value)
+        }
+    }))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct World {
+    filled_tiles: SparseGrid<Tile>,
+}
+
+impl World {
+    pub fn from_input(input: &str) -> ParseResult<Self> {
+        let mut filled_tiles = SparseGrid::default();
+        filled_tiles.insert((500, 0), Tile::Spring);
+        let mut vein_count = 0;
+        for line in lines(input).filter(|line| !line.text.trim().is_empty()) {
+            filled_tiles.extend(parse_vein(line)?.map(|c| (c, Tile::Clay)));
+            vein_count += 1;
+        }
+        if vein_count == 0 {
+            return Err(end_of_input(input, "a vein of clay"));
+        }
+        Ok(Self { filled_tiles })
+    }
+
+    fn get_clay_bounds(&self) -> (usize, usize) {
+        let clay_tile_y_coordinates: Vec<_> = self
+            .filled_tiles
+            .iter()
+            .filter(|(_, t)| **t == Tile::Clay)
+            .map(|((_x, y), _)| y)
+            .collect();
+        let min_bound = clay_tile_y_coordinates.iter().min().unwrap();
+        let max_bound = clay_tile_y_coordinates.iter().max().unwrap();
+        (*min_bound, *max_bound)
+    }
+
+    pub fn simulate_water(&mut self) {
+        let mut flow = WaterFlow::new(mem::take(self));
+        while flow.fill_next() {}
+        *self = flow.into_world();
+    }
+
+    fn is_valid_position(&self, p: Position) -> bool {
+        let below_position = (p.0, p.1 + 1);
+        let below_tile = self.filled_tiles.get(below_position);
+        !matches!(below_tile, Some(Tile::Flow))
+    }
+
+    fn settle_around(&mut self, position: Position) -> bool {
+        fn find_flow_to_clay<I>(world: &World, x_values: I, y: usize) -> Option<Vec<Position>>
+        where
+            I: IntoIterator<Item = usize>,
+        {
+            let mut flows = vec![];
+            for x in x_values {
+                let current_position = (x, y);
+                let current_tile = world.filled_tiles.get(current_position);
+                current_tile?;
+                let current_tile = current_tile.unwrap();
+                match current_tile {
+                    Tile::Flow => flows.push(current_position),
+                    Tile::Water | Tile::Spring => continue,
+                    Tile::Clay => break,
+                };
+            }
+            Some(flows)
+        }
+        let left_flows = find_flow_to_clay(self, (0..position.0).rev(), position.1);
+        let right_flows = find_flow_to_clay(self, position.0.., position.1);
+        if left_flows.is_none() || right_flows.is_none() {
+            return false;
+        }
+        let left_flows = left_flows.unwrap();
+        let right_flows = right_flows.unwrap();
+        for flow in left_flows.iter().chain(right_flows.iter()) {
+            self.filled_tiles.insert(*flow, Tile::Water);
+        }
+        true
+    }
+
+    pub fn count_water_and_flow(&self) -> u32 {
+        let (min_clay_bound, max_clay_bound) = self.get_clay_bounds();
+        self.filled_tiles
+            .iter()
+            .filter(|((_x, y), t)| {
+                *y >= min_clay_bound
+                    && *y <= max_clay_bound
+                    && (**t == Tile::Water || **t == Tile::Flow)
+            })
+            .count() as u32
+    }
+
+    pub fn count_water(&self) -> u32 {
+        let (min_clay_bound, max_clay_bound) = self.get_clay_bounds();
+        self.filled_tiles
+            .iter()
+            .filter(|((_x, y), t)| {
+                *y >= min_clay_bound && *y <= max_clay_bound && (**t == Tile::Water)
+            })
+            .count() as u32
+    }
+
+    fn area_to_string(&self, bounds: Bounds) -> String {
+        self.filled_tiles
+            .render_area(display_bounds(bounds), |_, tile| {
+                tile.map_or('.', Tile::to_char)
+            })
+    }
+
+    fn log_area_around(&self, position: Position) {
+        let (x, y) = position;
+        trace!(
+            "Filling from {:?}:\n{}",
+            position,
+            self.area_to_string(Bounds::new(
+                (x.saturating_sub(10), y.saturating_sub(10)),
+                (x.saturating_add(10), y.saturating_add(10)),
+            ))
+        )
+    }
+}
+
+/// The bounds with an extra column of tiles on each side, except at the edge of possible
+/// coordinates, to show there is no water or anything in those tiles
+fn display_bounds(bounds: Bounds) -> Bounds {
+    Bounds::new(
+        (bounds.min.0.saturating_sub(1), bounds.min.1),
+        (bounds.max.0.saturating_add(1), bounds.max.1),
+    )
+}
+
+impl Draw for World {
+    fn draw(&self) -> Image {
+        match self.filled_tiles.bounds() {
+            Some(bounds) => {
+                Image::from_area(&self.filled_tiles, display_bounds(bounds), |_, tile| {
+                    tile.map_or(SAND_COLOR, Tile::color)
+                })
+            }
+            None => Image::new(0, 0, SAND_COLOR),
+        }
+    }
+}
+
+impl fmt::Display for World {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.filled_tiles.bounds() {
+            Some(bounds) => write!(f, "{}", self.area_to_string(bounds)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Water flowing from the spring, filled in one position at a time
+pub struct WaterFlow {
+    world: World,
+    max_y: usize,
+    downward_stack: Vec<Position>,
+    downward_history: Vec<Position>,
+    current_position: Position,
+}
+
+impl WaterFlow {
+    pub fn new(world: World) -> Self {
+        let (spring_x, spring_y) = world
+            .filled_tiles
+            .iter()
+            .find(|(_, t)| **t == Tile::Spring)
+            .map(|(c, _)| c)
+            .unwrap();
+        let (_, max_y) = world.get_clay_bounds();
+        let start = (spring_x, spring_y + 1);
+        Self {
+            world,
+            max_y,
+            downward_stack: vec![start],
+            downward_history: vec![start],
+            current_position: start,
+        }
+    }
+
+    /// Fills the water flowing from the next position on the stack, returning false once there
+    /// was none left
+    fn fill_next(&mut self) -> bool {
+        let current_position = match self.downward_stack.pop() {
+            Some(position) => position,
+            None => return false,
+        };
+        self.current_position = current_position;
+        self.world.log_area_around(current_position);
+        let (current_x, current_y) = current_position;
+        self.world.filled_tiles.insert(current_position, Tile::Flow);
+        if current_y == self.max_y {
+            self.downward_history.clear();
+            return true;
+        }
+        let downward_position = (current_x, current_y + 1);
+        if !self.world.filled_tiles.contains(downward_position) {
+            self.downward_stack.push(downward_position);
+            self.downward_history.push(current_position);
+            return true;
+        }
+        let mut horizontal_stack = vec![current_position];
+        while let Some(current_position) = horizontal_stack.pop() {
+            let (current_x, current_y) = current_position;
+            self.world.filled_tiles.insert(current_position, Tile::Flow);
+            let downward_position = (current_x, current_y + 1);
+            if !self.world.filled_tiles.contains(downward_position) {
+                self.downward_stack.push(downward_position);
+                self.downward_history.push(current_position);
+                continue;
+            }
+            let valid_horizontal: Vec<_> =
+                vec![(current_x - 1, current_y), (current_x + 1, current_y)]
+                    .into_iter()
+                    .filter(|p| {
+                        !self.world.filled_tiles.contains(*p) && self.world.is_valid_position(*p)
+                    })
+                    .collect();
+            if !valid_horizontal.is_empty() {
+                horizontal_stack.extend(valid_horizontal.iter());
+            }
+        }
+        let has_settled = self.world.settle_around(current_position);
+        if has_settled && !self.downward_history.is_empty() {
+            self.downward_stack
+                .push(self.downward_history.pop().unwrap());
+        }
+        true
+    }
+
+    pub fn into_world(self) -> World {
+        self.world
+    }
+}
+
+impl Simulation for WaterFlow {
+    fn step(&mut self) -> bool {
+        self.fill_next();
+        !self.downward_stack.is_empty()
+    }
+
+    fn focus(&self) -> Option<(usize, usize)> {
+        // The rendering starts a column left of the leftmost tile
+        let bounds = self.world.filled_tiles.bounds()?;
+        let (x, y) = self.current_position;
+        Some((x - bounds.min.0.saturating_sub(1), y - bounds.min.1))
+    }
+}
+
+impl Draw for WaterFlow {
+    fn draw(&self) -> Image {
+        self.world.draw()
+    }
+}
+
+impl fmt::Display for WaterFlow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.world.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input");
+    const SAMPLE_INPUT: &str = include_str!("../sample-input");
+    const SAMPLE_WORLD: &str = include_str!("../sample-world");
+
+    #[test]
+    fn it_parses_sample_correctly() {
+        let world = World::from_input(SAMPLE_INPUT).unwrap();
+        assert_eq!(format!("{}", world), SAMPLE_WORLD);
+    }
+
+    #[test]
+    fn it_reports_veins_along_one_axis_twice() {
+        assert_eq!(
+            World::from_input("x=495, y=2..7\ny=7, y=495..501")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 6: expected x, found \"y\""
+        );
+    }
+
+    #[test]
+    fn it_solves_part_one_sample_correctly() {
+        let mut world = World::from_input(SAMPLE_INPUT).unwrap();
+        world.simulate_water();
+        assert_eq!(world.count_water_and_flow(), 57);
+    }
+
+    #[test]
+    fn it_solves_part_two_sample_correctly() {
+        let mut world = World::from_input(SAMPLE_INPUT).unwrap();
+        world.simulate_water();
+        assert_eq!(world.count_water(), 29);
+    }
+
+    #[test]
+    fn it_renders_settled_water() {
+        let mut world = World::from_input(SAMPLE_INPUT).unwrap();
+        world.simulate_water();
+        common::assert_snapshot(
+            env!("CARGO_MANIFEST_DIR"),
+            "sample-input",
+            &world.to_string(),
+        );
+    }
+
+    #[test]
+    fn it_solves_part_one_real_correctly() {
+        let mut world = World::from_input(INPUT).unwrap();
+        world.simulate_water();
+        assert_eq!(world.count_water_and_flow(), 31412);
+    }
+
+    #[test]
+    fn it_solves_part_two_real_correctly() {
+        let mut world = World::from_input(INPUT).unwrap();
+        world.simulate_water();
+        assert_eq!(world.count_water(), 25857);
+    }
+}
@@ -0,0 +1,250 @@
+use animation::Simulation;
+use common::{end_of_input, ParseError, ParseResult, Solution};
+use cycle::state_after;
+use grid::Grid;
+use log::{debug, trace};
+use raster::{Color, Draw, Image};
+use std::fmt;
+
+pub const PART_ONE_MINUTES: usize = 10;
+pub const PART_TWO_MINUTES: usize = 1_000_000_000;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = World;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(World::from_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        let mut world = input.clone();
+        world.simulate(PART_ONE_MINUTES);
+        world.get_resource_value()
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        let mut world = input.clone();
+        world.simulate(PART_TWO_MINUTES);
+        world.get_resource_value()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct World {
+    acres: Grid<Acre>,
+}
+
+impl World {
+    pub fn from_input(input: &str) -> ParseResult<Self> {
+        if input.trim().is_empty() {
+            return Err(end_of_input(input, "an acre, . | or #"));
+        }
+        let acres = Grid::try_from_char_map(input.trim_end(), |position, c| {
+            Acre::from_char(c).ok_or_else(|| ParseError::at_cell(position, "an acre, . | or #", c))
+        })?;
+        Ok(Self { acres })
+    }
+
+    fn tick(&mut self) {
+        let initial_acres = self.acres.clone();
+        for (position, acre) in self.acres.iter_mut() {
+            let adjacent_acres: Vec<_> =
+                initial_acres.neighbors8(position).map(|(_, a)| a).collect();
+            acre.tick(&adjacent_acres);
+        }
+    }
+
+    pub fn simulate(&mut self, minutes: usize) {
+        trace!("Initial state:\n{}", self);
+        let mut current_minute = 0;
+        let tick = |world: &World| {
+            let mut world = world.clone();
+            world.tick();
+            current_minute += 1;
+            trace!(
+                "After {} minute{}:\n{}",
+                current_minute,
+                if current_minute > 1 { "s" } else { "" },
+                world
+            );
+            world
+        };
+        *self = state_after(self.clone(), tick, minutes);
+        if current_minute < minutes {
+            debug!("Cycle at minute {}", current_minute);
+            trace!(
+                "After {} minute{}:\n{}",
+                minutes,
+                if minutes > 1 { "s" } else { "" },
+                self
+            );
+        }
+    }
+
+    pub fn get_resource_value(&self) -> usize {
+        let acres: Vec<_> = self.acres.values().collect();
+        let wooded_count = count_wooded(&acres);
+        let lumberyard_count = count_lumberyards(&acres);
+        wooded_count * lumberyard_count
+    }
+}
+
+impl Simulation for World {
+    fn step(&mut self) -> bool {
+        self.tick();
+        true
+    }
+}
+
+impl Draw for World {
+    fn draw(&self) -> Image {
+        Image::from_grid(&self.acres, |_, acre| acre.map_or([0, 0, 0], Acre::color))
+    }
+}
+
+impl fmt::Display for World {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.acres.fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Acre {
+    Open,
+    Wooded,
+    Lumberyard,
+}
+
+impl Acre {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Acre::Open),
+            '|' => Some(Acre::Wooded),
+            '#' => Some(Acre::Lumberyard),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Acre::Open => '.',
+            Acre::Wooded => '|',
+            Acre::Lumberyard => '#',
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Acre::Open => [200, 190, 140],
+            Acre::Wooded => [30, 120, 40],
+            Acre::Lumberyard => [110, 70, 40],
+        }
+    }
+
+    fn tick(&mut self, adjacent_acres: &[&Acre]) {
+        match self {
+            Acre::Open => {
+                let adjacent_wooded_count = count_wooded(adjacent_acres);
+                if adjacent_wooded_count >= 3 {
+                    *self = Acre::Wooded;
+                }
+            }
+            Acre::Wooded => {
+                let adjacent_lumberyard_count = count_lumberyards(adjacent_acres);
+                if adjacent_lumberyard_count >= 3 {
+                    *self = Acre::Lumberyard;
+                }
+            }
+            Acre::Lumberyard => {
+                let adjacent_lumberyard_count = count_lumberyards(adjacent_acres);
+                let adjacent_wooded_count = count_wooded(adjacent_acres);
+                if adjacent_lumberyard_count < 1 || adjacent_wooded_count < 1 {
+                    *self = Acre::Open;
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Acre {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+fn count_lumberyards(acres: &[&Acre]) -> usize {
+    acres.iter().filter(|&a| **a == Acre::Lumberyard).count()
+}
+
+fn count_wooded(acres: &[&Acre]) -> usize {
+    acres.iter().filter(|&a| **a == Acre::Wooded).count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input");
+    const SAMPLE_INPUT: &str = include_str!("../sample-input");
+    const SAMPLE_SOLUTION: usize = 1147;
+    const PART_ONE_SOLUTION: usize = 480150;
+    const PART_TWO_SOLUTION: usize = 233020;
+
+    #[test]
+    fn it_reports_invalid_acres() {
+        assert_eq!(
+            World::from_input(".|\n#x\n").unwrap_err().to_string(),
+            "line 2, column 2: expected an acre, . | or #, found \"x\""
+        );
+    }
+
+    #[test]
+    fn it_parses_input_correctly() {
+        // relies on correct Display implementations
+        let world = World::from_input(SAMPLE_INPUT).unwrap();
+        assert_eq!(format!("{}", world), SAMPLE_INPUT);
+
+        let world = World::from_input(INPUT).unwrap();
+        assert_eq!(format!("{}", world), INPUT);
+    }
+
+    #[test]
+    fn it_solves_part_one_correctly() {
+        let mut world = World::from_input(SAMPLE_INPUT).unwrap();
+        world.simulate(PART_ONE_MINUTES);
+        assert_eq!(world.get_resource_value(), SAMPLE_SOLUTION);
+
+        let mut world = World::from_input(INPUT).unwrap();
+        world.simulate(PART_ONE_MINUTES);
+        assert_eq!(world.get_resource_value(), PART_ONE_SOLUTION);
+    }
+
+    #[test]
+    fn it_renders_each_minute() {
+        let mut world = World::from_input(SAMPLE_INPUT).unwrap();
+        let mut rendered = format!("Initial state:\n{}", world);
+        for minute in 1..=PART_ONE_MINUTES {
+            world.tick();
+            rendered += &format!(
+                "\nAfter {} minute{}:\n{}",
+                minute,
+                if minute > 1 { "s" } else { "" },
+                world
+            );
+        }
+        common::assert_snapshot(env!("CARGO_MANIFEST_DIR"), "sample-input", &rendered);
+    }
+
+    #[test]
+    fn it_solves_part_two_correctly() {
+        let mut world = World::from_input(INPUT).unwrap();
+        world.simulate(PART_TWO_MINUTES);
+        assert_eq!(world.get_resource_value(), PART_TWO_SOLUTION);
+    }
+}
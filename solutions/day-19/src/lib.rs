@@ -0,0 +1,51 @@
+use common::{Result as SolutionResult, Solution};
+
+pub type Program = elfcode::Program<u32, 6>;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Program;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> SolutionResult<Self::Input> {
+        let mut program: Program = input.parse()?;
+        program.optimize();
+        Ok(program)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        let mut program = input.clone();
+        program.run();
+        program.registers[0]
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        let mut program = input.clone();
+        program.registers[0] = 1;
+        program.run();
+        program.registers[0]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../sample-input");
+    const INPUT: &str = include_str!("../input");
+
+    #[test]
+    fn it_solves_part_one_correctly() {
+        let mut program: Program = SAMPLE_INPUT.parse().unwrap();
+        program.run();
+        assert_eq!(program.registers[0], 7);
+    }
+
+    #[test]
+    fn it_solves_part_two_by_running_the_program() {
+        let program = Day19::parse(INPUT).unwrap();
+        assert_eq!(Day19::part_two(&program), 10750428);
+    }
+}
@@ -0,0 +1,200 @@
+use common::{end_of_input, section_lines, ParseResult, Solution};
+use geometry::{Point, Point2};
+use pathfinding::bfs_distances;
+use std::collections::{HashMap, HashSet};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Regex;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        solve_part_one(&find_door_counts(input))
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        solve_part_two(&find_door_counts(input))
+    }
+}
+
+/// A regex of directions and balanced groups like `^N(E|W)$`, only built by `parse_input`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regex(String);
+
+impl Regex {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Checks that the input is a regex of directions and balanced groups like `^N(E|W)$`
+pub fn parse_input(input: &str) -> ParseResult<Regex> {
+    let regex = input.trim();
+    let line = section_lines(input, regex)
+        .next()
+        .ok_or_else(|| end_of_input(input, "a regex starting with ^"))?;
+    let mut cursor = line.cursor();
+    cursor.literal("^")?;
+    let mut depth = 0;
+    loop {
+        match cursor.rest().chars().next() {
+            Some(')') | Some('|') if depth == 0 => {
+                return Err(cursor.error("a direction, ( or $ outside of a group"));
+            }
+            Some('$') if depth > 0 => return Err(cursor.error("a ) closing the group")),
+            _ => {}
+        }
+        match cursor.char("a direction, group or $", |c| "NESW(|)$".contains(c))? {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '$' => break,
+            _ => {}
+        }
+    }
+    cursor.end()?;
+    Ok(Regex(String::from(regex)))
+}
+
+/// Builds the map from the regex, returning the rooms each room has doors to
+fn find_doors(regex: &Regex) -> HashMap<Point2, Vec<Point2>> {
+    let s = &regex.0[1..regex.0.len() - 1];
+    let mut doors: HashMap<Point2, Vec<Point2>> = HashMap::new();
+    let mut positions = HashSet::new();
+    positions.insert(Point2::ORIGIN);
+    // The positions at the start of each open group and the positions its branches ended at
+    let mut groups: Vec<(HashSet<Point2>, HashSet<Point2>)> = vec![];
+    for c in s.chars() {
+        match c {
+            '(' => groups.push((positions.clone(), HashSet::new())),
+            '|' => {
+                let (starts, ends) = groups.last_mut().unwrap();
+                ends.extend(positions.drain());
+                positions = starts.clone();
+            }
+            ')' => {
+                let (_, ends) = groups.pop().unwrap();
+                positions.extend(ends);
+            }
+            d if "NESW".contains(d) => {
+                let step = match d {
+                    'N' => Point::new([0, -1]),
+                    'E' => Point::new([1, 0]),
+                    'S' => Point::new([0, 1]),
+                    'W' => Point::new([-1, 0]),
+                    _ => unreachable!(),
+                };
+                positions = positions
+                    .into_iter()
+                    .map(|position| {
+                        let next = position + step;
+                        doors.entry(position).or_default().push(next);
+                        doors.entry(next).or_default().push(position);
+                        next
+                    })
+                    .collect();
+            }
+            _ => unreachable!(),
+        }
+    }
+    doors
+}
+
+/// The fewest doors that have to be passed through to reach each room
+pub fn find_door_counts(regex: &Regex) -> HashMap<Point2, usize> {
+    let doors = find_doors(regex);
+    bfs_distances(Point2::ORIGIN, |p| {
+        doors.get(p).cloned().unwrap_or_default()
+    })
+    .into_iter()
+    .map(|(p, count)| (p, count as usize))
+    .collect()
+}
+
+pub fn solve_part_one(door_counts: &HashMap<Point2, usize>) -> usize {
+    *door_counts.values().max().unwrap()
+}
+
+pub fn solve_part_two(door_counts: &HashMap<Point2, usize>) -> usize {
+    door_counts.values().filter(|v| **v >= 1000).count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLES: [(&str, usize); 5] = [
+        ("^WNE$", 3),
+        ("^ENWWW(NEEE|SSE(EE|N))$", 10),
+        ("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$", 18),
+        ("^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$", 23),
+        (
+            "^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$",
+            31,
+        ),
+    ];
+
+    #[test]
+    fn it_reports_unbalanced_groups() {
+        assert_eq!(parse_input("^N(E|W)$\n").unwrap().as_str(), "^N(E|W)$");
+        assert_eq!(
+            parse_input("^N(E|W$").unwrap_err().to_string(),
+            "line 1, column 7: expected a ) closing the group, found \"$\""
+        );
+        assert_eq!(
+            parse_input("^NE)W$").unwrap_err().to_string(),
+            "line 1, column 4: expected a direction, ( or $ outside of a group, found \")\""
+        );
+    }
+
+    #[test]
+    fn it_solves_first_sample_correctly() {
+        let (regex, expected) = SAMPLES[0];
+        let door_counts = find_door_counts(&parse_input(regex).unwrap());
+        assert_eq!(solve_part_one(&door_counts), expected);
+    }
+
+    #[test]
+    fn it_solves_second_sample_correctly() {
+        let (regex, expected) = SAMPLES[1];
+        let door_counts = find_door_counts(&parse_input(regex).unwrap());
+        assert_eq!(solve_part_one(&door_counts), expected);
+    }
+
+    #[test]
+    fn it_solves_third_sample_correctly() {
+        let (regex, expected) = SAMPLES[2];
+        let door_counts = find_door_counts(&parse_input(regex).unwrap());
+        assert_eq!(solve_part_one(&door_counts), expected);
+    }
+
+    #[test]
+    fn it_solves_fourth_sample_correctly() {
+        let (regex, expected) = SAMPLES[3];
+        let door_counts = find_door_counts(&parse_input(regex).unwrap());
+        assert_eq!(solve_part_one(&door_counts), expected);
+    }
+
+    #[test]
+    fn it_solves_fifth_sample_correctly() {
+        let (regex, expected) = SAMPLES[4];
+        let door_counts = find_door_counts(&parse_input(regex).unwrap());
+        assert_eq!(solve_part_one(&door_counts), expected);
+    }
+
+    #[test]
+    fn it_solves_many_option_branches_correctly() {
+        // The SSSS branch passes back through the start, so its rooms are closer than the
+        // regex suggests and the furthest room is west of the N branch
+        let regex = "^NNN(N|E|SSSS)W$";
+        let door_counts = find_door_counts(&parse_input(regex).unwrap());
+        assert_eq!(solve_part_one(&door_counts), 5);
+    }
+}
@@ -0,0 +1,98 @@
+use common::{Result as SolutionResult, Solution};
+use cycle::find_cycle_by;
+use elfcode::{Decompiled, HaltCheck};
+
+pub type Register = u64;
+pub type Program = elfcode::Program<Register, 6>;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = HaltingProgram;
+    type PartOne = Register;
+    type PartTwo = Register;
+
+    fn parse(input: &str) -> SolutionResult<Self::Input> {
+        let mut program: Program = input.parse()?;
+        let halt_check = Decompiled::new(&program)
+            .find_halt_check(0)
+            .ok_or("Program never compares register 0 to decide whether to halt")?;
+        program.optimize();
+        Ok(HaltingProgram {
+            program,
+            halt_check,
+        })
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        let HaltingProgram {
+            program,
+            halt_check,
+        } = input;
+        let mut program = program.clone();
+        program.run_to_line(halt_check.line);
+        program.registers[halt_check.register]
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        solve_part_two(input)
+    }
+}
+
+/// A program that halts once register 0 equals the register in its halt check
+#[derive(Debug, Clone)]
+pub struct HaltingProgram {
+    pub program: Program,
+    pub halt_check: HaltCheck,
+}
+
+pub fn solve_part_two(input: &HaltingProgram) -> Register {
+    let HaltCheck { line, register } = input.halt_check;
+    let mut program = input.program.clone();
+    program.run_to_line(line);
+    // Only the value compared with register 0 carries over to the next time it is compared
+    let cycle = find_cycle_by(
+        program.registers,
+        |&registers| {
+            program.registers = registers;
+            program.run_to_line(line);
+            program.registers
+        },
+        |registers| registers[register],
+    );
+    cycle.state_at(cycle.start + cycle.length - 1)[register]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input");
+
+    #[test]
+    fn it_solves_part_one_correctly() {
+        let program = Day21::parse(INPUT).unwrap();
+        assert_eq!(Day21::part_one(&program), 13970209);
+    }
+
+    #[test]
+    fn it_solves_part_two_correctly() {
+        let program = Day21::parse(INPUT).unwrap();
+        assert_eq!(Day21::part_two(&program), 6267260);
+    }
+
+    #[test]
+    fn it_finds_the_halt_check() {
+        let input = Day21::parse(INPUT).unwrap();
+        assert_eq!(
+            input.halt_check,
+            HaltCheck {
+                line: 28,
+                register: 2
+            }
+        );
+        let swapped = Day21::parse(&INPUT.replace("eqrr 2 0 3", "eqrr 0 2 3")).unwrap();
+        assert_eq!(swapped.halt_check, input.halt_check);
+        assert!(Day21::parse("#ip 4\nseti 0 0 4").is_err());
+    }
+}
@@ -0,0 +1,358 @@
+use common::{end_of_input, lines, ParseResult, Solution};
+use geometry::Point2;
+use grid::{Grid, Position};
+use log::{debug, log_enabled, Level};
+use pathfinding::a_star;
+use raster::{Color, Draw, Image};
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Cave;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Cave::from_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        input.calculate_total_risk_level()
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        let (_, time) = input.clone().find_path_to_target();
+        time
+    }
+}
+
+const MOVE_COST: u64 = 1;
+const TOOL_CHANGE_COST: u64 = 7;
+
+/// The grid position of a point in the cave, which never has negative coordinates
+fn to_position(point: Point2) -> Position {
+    Position::try_from(point).expect("Cave points are never negative")
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum RegionType {
+    Rocky,
+    Narrow,
+    Wet,
+}
+
+impl RegionType {
+    fn from_erosion_level(level: usize) -> Self {
+        use crate::RegionType::*;
+        match level % 3 {
+            0 => Rocky,
+            1 => Wet,
+            2 => Narrow,
+            _ => unreachable!(),
+        }
+    }
+
+    fn find_risk_level(self) -> usize {
+        use crate::RegionType::*;
+        match self {
+            Rocky => 0,
+            Narrow => 2,
+            Wet => 1,
+        }
+    }
+
+    fn to_char(self) -> char {
+        use crate::RegionType::*;
+        match self {
+            Rocky => '.',
+            Wet => '=',
+            Narrow => '|',
+        }
+    }
+
+    fn color(self) -> Color {
+        use crate::RegionType::*;
+        match self {
+            Rocky => [150, 150, 150],
+            Wet => [60, 110, 200],
+            Narrow => [70, 60, 50],
+        }
+    }
+}
+
+const MOUTH_AND_TARGET_COLOR: Color = [220, 40, 40];
+
+#[derive(Debug, Clone)]
+pub struct Cave {
+    regions: Grid<RegionType>,
+    erosion_levels: Grid<usize>,
+    target: Point2,
+    depth: usize,
+}
+
+impl Cave {
+    fn new(target: Point2, depth: usize) -> Self {
+        let mut cave = Self {
+            regions: Grid::new(0, 0, RegionType::Rocky),
+            erosion_levels: Grid::new(0, 0, 0),
+            target,
+            depth,
+        };
+        cave.generate_regions(to_position(target));
+        cave
+    }
+
+    /// Parses a scan like `depth: 510` and `target: 10,10` on separate lines
+    pub fn from_input(input: &str) -> ParseResult<Self> {
+        let mut lines = lines(input).filter(|line| !line.text.trim().is_empty());
+        let line = lines.next().ok_or_else(|| end_of_input(input, "a depth"))?;
+        let mut cursor = line.cursor();
+        cursor.literal("depth: ")?;
+        let depth = cursor.number("a depth")?;
+        cursor.end()?;
+        let line = lines
+            .next()
+            .ok_or_else(|| end_of_input(input, "a target"))?;
+        let mut cursor = line.cursor();
+        cursor.literal("target: ")?;
+        let x: usize = cursor.number("an x coordinate")?;
+        cursor.literal(",")?;
+        let y: usize = cursor.number("a y coordinate")?;
+        cursor.end()?;
+        Ok(Self::new(Point2::from((x, y)), depth))
+    }
+
+    fn generate_regions(&mut self, new_bounds: (usize, usize)) {
+        let (new_x_bound, new_y_bound) = new_bounds;
+        let (current_x_bound, current_y_bound) = self.get_current_bounds();
+        if new_x_bound < current_x_bound && new_y_bound < current_y_bound {
+            return;
+        }
+        let x_bound = usize::max(new_x_bound, current_x_bound);
+        let y_bound = usize::max(new_y_bound, current_y_bound);
+        self.regions
+            .resize(x_bound + 1, y_bound + 1, RegionType::Rocky);
+        self.erosion_levels.resize(x_bound + 1, y_bound + 1, 0);
+        for y in 0..=y_bound {
+            for x in 0..=x_bound {
+                if y < current_y_bound && x < current_x_bound {
+                    continue;
+                }
+                let erosion_level = self.calculate_erosion_level(Point2::from((x, y)));
+                self.regions[(x, y)] = RegionType::from_erosion_level(erosion_level);
+            }
+        }
+    }
+
+    fn get_current_bounds(&self) -> (usize, usize) {
+        (self.regions.width(), self.regions.height())
+    }
+
+    fn calculate_geologic_index(&self, coordinate: Point2) -> usize {
+        match to_position(coordinate) {
+            (0, 0) => 0,
+            c if c == to_position(self.target) => 0,
+            (x, 0) => x * 16807,
+            (0, y) => y * 48271,
+            (x, y) => self.erosion_levels[(x - 1, y)] * self.erosion_levels[(x, y - 1)],
+        }
+    }
+
+    fn calculate_erosion_level(&mut self, coordinate: Point2) -> usize {
+        let erosion_level = (self.calculate_geologic_index(coordinate) + self.depth) % 20183;
+        self.erosion_levels[to_position(coordinate)] = erosion_level;
+        erosion_level
+    }
+
+    pub fn calculate_total_risk_level(&self) -> usize {
+        self.regions.values().map(|r| r.find_risk_level()).sum()
+    }
+
+    fn get_adjacent_regions(&mut self, coordinate: Point2) -> Vec<(Point2, RegionType)> {
+        let (x, y) = to_position(coordinate);
+        let (current_x_bound, current_y_bound) = self.get_current_bounds();
+        if x + 1 >= current_x_bound || y + 1 >= current_y_bound {
+            self.generate_regions((x + 1, y + 1));
+        }
+        self.regions
+            .neighbors4((x, y))
+            .map(|(c, &r)| (Point2::from(c), r))
+            .collect()
+    }
+
+    pub fn find_path_to_target(&mut self) -> (Vec<State>, usize) {
+        let start_state = State {
+            position: Point2::ORIGIN,
+            equipped_tool: Tool::Torch,
+        };
+        let goal_state = State {
+            position: self.target,
+            equipped_tool: Tool::Torch,
+        };
+        let path = a_star(
+            start_state,
+            |state| state.next_states(self),
+            |state| state.position.manhattan_distance(&goal_state.position),
+            |state| *state == goal_state,
+        )
+        .unwrap();
+        (path.states, path.cost as usize)
+    }
+}
+
+impl fmt::Display for Cave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rendered = self.regions.render(|coordinate, r| {
+            if coordinate == (0, 0) {
+                'M'
+            } else if coordinate == to_position(self.target) {
+                'T'
+            } else {
+                r.unwrap().to_char()
+            }
+        });
+        write!(f, "{}", rendered)
+    }
+}
+
+impl Draw for Cave {
+    fn draw(&self) -> Image {
+        Image::from_grid(&self.regions, |coordinate, r| {
+            if coordinate == (0, 0) || coordinate == to_position(self.target) {
+                MOUTH_AND_TARGET_COLOR
+            } else {
+                r.unwrap().color()
+            }
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+enum Tool {
+    ClimbingGear,
+    Torch,
+    Neither,
+}
+
+impl Tool {
+    fn is_allowed_in_region(self, region: RegionType) -> bool {
+        use crate::RegionType::*;
+        use crate::Tool::*;
+        match region {
+            Rocky => [ClimbingGear, Torch].contains(&self),
+            Wet => [ClimbingGear, Neither].contains(&self),
+            Narrow => [Torch, Neither].contains(&self),
+        }
+    }
+
+    fn find_other_allowed_tool(self, current_region: RegionType) -> Self {
+        use crate::Tool::*;
+        *[ClimbingGear, Torch, Neither]
+            .iter()
+            .find(|t| **t != self && t.is_allowed_in_region(current_region))
+            .unwrap()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Copy)]
+pub struct State {
+    position: Point2,
+    equipped_tool: Tool,
+}
+
+impl State {
+    fn next_states(&self, cave: &mut Cave) -> Vec<(State, u64)> {
+        let current_region = cave.regions[to_position(self.position)];
+        let tool_change_state = (
+            State {
+                position: self.position,
+                equipped_tool: self.equipped_tool.find_other_allowed_tool(current_region),
+            },
+            TOOL_CHANGE_COST,
+        );
+        let adjacent_regions = cave.get_adjacent_regions(self.position);
+        let move_states = adjacent_regions
+            .iter()
+            .filter(|(_, r)| self.equipped_tool.is_allowed_in_region(*r))
+            .map(|&(c, _)| State {
+                position: c,
+                equipped_tool: self.equipped_tool,
+            })
+            .zip(iter::repeat(MOVE_COST));
+        let mut result = vec![tool_change_state];
+        result.extend(move_states);
+        result
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Position: {:?} Tool: {:?}",
+            to_position(self.position),
+            self.equipped_tool
+        )
+    }
+}
+
+/// Logs the cave with the position and tool of each state of the path
+pub fn log_path(cave: &Cave, states: &[State]) {
+    if !log_enabled!(Level::Debug) {
+        return;
+    }
+    debug!("{}", cave);
+    for state in states.iter() {
+        let result = cave.regions.render(|coordinate, r| {
+            if coordinate == to_position(state.position) {
+                match state.equipped_tool {
+                    Tool::ClimbingGear => 'G',
+                    Tool::Torch => 'F',
+                    Tool::Neither => 'n',
+                }
+            } else if coordinate == (0, 0) {
+                'M'
+            } else if coordinate == to_position(cave.target) {
+                'T'
+            } else {
+                r.unwrap().to_char()
+            }
+        });
+        debug!("{}\n{}", state, result);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("../sample-input");
+
+    #[test]
+    fn it_solves_part_one_correctly() {
+        let cave = Cave::new(Point2::new([10, 10]), 510);
+        assert_eq!(cave.calculate_total_risk_level(), 114);
+    }
+
+    #[test]
+    fn it_solves_part_two_correctly() {
+        let mut cave = Cave::new(Point2::new([10, 10]), 510);
+        let (states, time) = cave.find_path_to_target();
+        assert_eq!(time, 45);
+        log_path(&cave, &states);
+    }
+
+    #[test]
+    fn it_renders_the_cave() {
+        let mut cave = Cave::from_input(SAMPLE_INPUT).unwrap();
+        let mut rendered = format!("Up to the target:\n{}", cave);
+        cave.find_path_to_target();
+        rendered += &format!("\nAfter finding the path:\n{}", cave);
+        common::assert_snapshot(env!("CARGO_MANIFEST_DIR"), "sample-input", &rendered);
+    }
+}
@@ -0,0 +1,164 @@
+use common::{end_of_input, lines, Line, ParseResult, Solution};
+use geometry::{BoundingBox, Point, Point3};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<Bot>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let bots: Vec<_> = lines(input)
+            .filter(|line| !line.text.trim().is_empty())
+            .map(Bot::from_input)
+            .collect::<ParseResult<_>>()?;
+        if bots.is_empty() {
+            return Err(end_of_input(input, "a nanobot").into());
+        }
+        Ok(bots)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        solve_part_one(input)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        solve_part_two(input)
+    }
+}
+
+pub struct Bot {
+    position: Point3,
+    range: usize,
+}
+
+impl Bot {
+    /// Parses a bot like `pos=<0,0,0>, r=4`
+    pub fn from_input(line: Line) -> ParseResult<Self> {
+        let mut cursor = line.cursor();
+        cursor.literal("pos=<")?;
+        let x = cursor.number("an x coordinate")?;
+        cursor.literal(",")?;
+        let y = cursor.number("a y coordinate")?;
+        cursor.literal(",")?;
+        let z = cursor.number("a z coordinate")?;
+        cursor.literal(">, r=")?;
+        let range = cursor.number("a signal radius")?;
+        cursor.end()?;
+        Ok(Self {
+            position: Point::new([x, y, z]),
+            range,
+        })
+    }
+}
+
+pub fn solve_part_one(bots: &[Bot]) -> usize {
+    let strongest_bot = bots.iter().max_by_key(|b| b.range).unwrap();
+    bots.iter()
+        .filter(|b| {
+            (strongest_bot.position.manhattan_distance(&b.position) as usize) < strongest_bot.range
+        })
+        .count()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Cube {
+    position: Point3,
+    size: usize,
+}
+
+impl Cube {
+    fn minimum_spanning_cube(bots: &[Bot]) -> Self {
+        let flat_positions: Vec<_> = bots
+            .iter()
+            .flat_map(|b| b.position.coordinates().to_vec())
+            .collect();
+        let min = *flat_positions.iter().min().unwrap();
+        let max = *flat_positions.iter().max().unwrap();
+        Self {
+            position: Point::splat(min),
+            size: (max - min) as usize,
+        }
+    }
+
+    fn bounds(&self) -> BoundingBox<3> {
+        BoundingBox::new(
+            self.position,
+            self.position + Point::splat(self.size as i64),
+        )
+    }
+
+    fn intersects_bot(&self, bot: &Bot) -> bool {
+        self.bounds().manhattan_distance_to(&bot.position) <= bot.range as u64
+    }
+
+    fn count_intersecting_bots(&self, bots: &[Bot]) -> usize {
+        bots.iter().filter(|b| self.intersects_bot(b)).count()
+    }
+
+    fn subdivide(&self) -> Vec<Cube> {
+        let size = self.size / 2;
+        BoundingBox::new(Point::ORIGIN, Point::splat(1))
+            .points()
+            .map(|offset| Cube {
+                position: self.position + offset * size as i64,
+                size,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct HeapWrapper {
+    cube: Cube,
+    bot_count: usize,
+}
+
+impl HeapWrapper {
+    fn from_cube(cube: Cube, bots: &[Bot]) -> Self {
+        let bot_count = cube.count_intersecting_bots(bots);
+        Self { cube, bot_count }
+    }
+}
+
+impl Ord for HeapWrapper {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.bot_count.cmp(&other.bot_count) {
+            Ordering::Equal => {
+                let other_distance = other.cube.position.manhattan_distance(&Point::ORIGIN);
+                let own_distance = self.cube.position.manhattan_distance(&Point::ORIGIN);
+                other_distance.cmp(&own_distance) // note reversed order
+            }
+            order => order,
+        }
+    }
+}
+
+impl PartialOrd for HeapWrapper {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub fn solve_part_two(bots: &[Bot]) -> usize {
+    let initial_cube = Cube::minimum_spanning_cube(bots);
+    let mut cubes = BinaryHeap::new();
+    cubes.push(HeapWrapper::from_cube(initial_cube, bots));
+    while !cubes.is_empty() {
+        let HeapWrapper { cube, .. } = cubes.pop().unwrap();
+        if cube.size == 0 {
+            return cube.position.manhattan_distance(&Point::ORIGIN) as usize;
+        }
+        cubes.extend(
+            cube.subdivide()
+                .into_iter()
+                .map(|c| HeapWrapper::from_cube(c, bots)),
+        );
+    }
+    panic!("No solution found")
+}
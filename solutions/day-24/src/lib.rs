@@ -0,0 +1,389 @@
+use common::{end_of_input, lines, Cursor, Line, ParseResult, Solution};
+use log::{debug, trace};
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::{Rc, Weak};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Group>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_groups(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        solve_part_one(input)
+    }
+
+    fn part_two(input: &Self::Input) -> Self::PartTwo {
+        solve_part_two(input)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum AttackType {
+    Radiation,
+    Bludgeoning,
+    Fire,
+    Slashing,
+    Cold,
+}
+
+impl AttackType {
+    fn from_name(s: &str) -> Option<Self> {
+        use crate::AttackType::*;
+        match s {
+            "radiation" => Some(Radiation),
+            "bludgeoning" => Some(Bludgeoning),
+            "fire" => Some(Fire),
+            "slashing" => Some(Slashing),
+            "cold" => Some(Cold),
+            _ => None,
+        }
+    }
+
+    fn parse(cursor: &mut Cursor) -> ParseResult<Self> {
+        let start = *cursor;
+        AttackType::from_name(cursor.word()).ok_or_else(|| start.error("an attack type"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Team {
+    ImmuneSystem,
+    Infection,
+}
+
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Team::ImmuneSystem => "Immune System",
+            Team::Infection => "Infection",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Group {
+    id: usize,
+    team: Team,
+    units: RefCell<u32>,
+    hit_points: u32,
+    attack_damage: u32,
+    attack_type: AttackType,
+    initiative: u32,
+    weaknesses: Vec<AttackType>,
+    immunities: Vec<AttackType>,
+    target: RefCell<Weak<Group>>,
+}
+
+impl Group {
+    /// Parses a group like `17 units each with 5390 hit points (weak to radiation) with an
+    /// attack that does 4507 fire damage at initiative 2`
+    fn from_input_line(line: Line, id: usize, team: Team) -> ParseResult<Self> {
+        let mut cursor = line.cursor();
+        let units = RefCell::new(cursor.number("a unit count")?);
+        cursor.literal(" units each with ")?;
+        let hit_points = cursor.number("hit points")?;
+        cursor.literal(" hit points")?;
+        let mut immunities = vec![];
+        let mut weaknesses = vec![];
+        if cursor.literal(" (").is_ok() {
+            loop {
+                let attack_types = if cursor.literal("immune to ").is_ok() {
+                    &mut immunities
+                } else if cursor.literal("weak to ").is_ok() {
+                    &mut weaknesses
+                } else {
+                    return Err(cursor.error("immune to or weak to"));
+                };
+                attack_types.push(AttackType::parse(&mut cursor)?);
+                while cursor.literal(", ").is_ok() {
+                    attack_types.push(AttackType::parse(&mut cursor)?);
+                }
+                if cursor.literal("; ").is_err() {
+                    break;
+                }
+            }
+            cursor.literal(")")?;
+        }
+        cursor.literal(" with an attack that does ")?;
+        let attack_damage = cursor.number("attack damage")?;
+        cursor.literal(" ")?;
+        let attack_type = AttackType::parse(&mut cursor)?;
+        cursor.literal(" damage at initiative ")?;
+        let initiative = cursor.number("an initiative")?;
+        cursor.end()?;
+        Ok(Self {
+            id,
+            team,
+            units,
+            hit_points,
+            attack_damage,
+            attack_type,
+            initiative,
+            weaknesses,
+            immunities,
+            target: RefCell::new(Weak::new()),
+        })
+    }
+
+    fn effective_power(&self) -> u32 {
+        *self.units.borrow() * self.attack_damage
+    }
+
+    fn calculate_attack_damage_to(&self, other: &Self) -> u32 {
+        if other.immunities.contains(&self.attack_type) {
+            return 0;
+        }
+        let mut damage = self.effective_power();
+        if other.weaknesses.contains(&self.attack_type) {
+            damage *= 2;
+        }
+        damage
+    }
+
+    fn key(&self) -> (usize, Team) {
+        (self.id, self.team)
+    }
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} group {}", self.team, self.id)
+    }
+}
+
+impl Hash for Group {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialEq for Group {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Group {}
+
+/// Parses the groups under their team's heading, numbering each team's groups from 1
+pub fn parse_groups(input: &str) -> ParseResult<Vec<Group>> {
+    let mut result = vec![];
+    let mut team = None;
+    let mut id = 0;
+    for line in lines(input).filter(|line| !line.text.trim().is_empty()) {
+        match line.text.trim_end() {
+            "Immune System:" => {
+                team = Some(Team::ImmuneSystem);
+                id = 0;
+            }
+            "Infection:" => {
+                team = Some(Team::Infection);
+                id = 0;
+            }
+            _ => {
+                let team = team
+                    .ok_or_else(|| line.mismatch("a team heading, Immune System: or Infection:"))?;
+                id += 1;
+                result.push(Group::from_input_line(line, id, team)?);
+            }
+        }
+    }
+    if result.is_empty() {
+        return Err(end_of_input(input, "a group"));
+    }
+    Ok(result)
+}
+
+fn simulate_combat(groups: &mut Vec<Rc<Group>>) {
+    let mut last_unit_count = count_units(groups);
+    while find_winning_team(groups).is_none() {
+        fight(groups);
+        let unit_count = count_units(groups);
+        if unit_count == last_unit_count {
+            debug!("Stalemate with {} units left", unit_count);
+            break;
+        }
+        last_unit_count = unit_count;
+    }
+}
+
+fn fight(groups: &mut Vec<Rc<Group>>) {
+    for team in [Team::ImmuneSystem, Team::Infection].iter() {
+        trace!("{}:", team);
+        for group in groups.iter().filter(|g| g.team == *team) {
+            trace!("Group {} contains {} units", group.id, group.units.borrow());
+        }
+    }
+    select_targets(groups);
+    resolve_attacks(groups);
+}
+
+fn select_targets(groups: &mut [Rc<Group>]) {
+    groups.sort_by_key(|g| Reverse((g.effective_power(), g.initiative)));
+    select_targets_for_team(groups, Team::Infection);
+    select_targets_for_team(groups, Team::ImmuneSystem);
+
+    // Groups are hashed by their (id, team) key which never changes, only their units do
+    #[allow(clippy::mutable_key_type)]
+    fn select_targets_for_team(groups: &mut [Rc<Group>], team: Team) {
+        let mut reserved_targets = HashSet::new();
+        for attacker in groups.iter().filter(|g| g.team == team) {
+            let target = groups
+                .iter()
+                .filter(|g| attacker.team != g.team && !reserved_targets.contains(g))
+                .max_by_key(|g| {
+                    let potential_damage = attacker.calculate_attack_damage_to(g);
+                    trace!(
+                        "{} group {} would deal defending group {} {} damage",
+                        attacker.team,
+                        attacker.id,
+                        g.id,
+                        potential_damage
+                    );
+                    (potential_damage, g.effective_power(), g.initiative)
+                });
+            if target.is_none() || attacker.calculate_attack_damage_to(target.unwrap()) == 0 {
+                *attacker.target.borrow_mut() = Weak::new();
+                continue;
+            }
+            let target = target.unwrap();
+            reserved_targets.insert(target);
+            *attacker.target.borrow_mut() = Rc::downgrade(target);
+        }
+    }
+}
+
+fn resolve_attacks(groups: &mut Vec<Rc<Group>>) {
+    groups.sort_by_key(|g| Reverse(g.initiative));
+    for attacker in groups.iter() {
+        let target = attacker.target.borrow().upgrade();
+        if target.is_none() || *attacker.units.borrow() == 0 {
+            continue;
+        }
+        let target = target.unwrap();
+        let damage = attacker.calculate_attack_damage_to(&target);
+        let killed_units = u32::min(damage / target.hit_points, *target.units.borrow());
+        *target.units.borrow_mut() -= killed_units;
+        trace!(
+            "{} group {} attacks defending group {}, killing {} units",
+            attacker.team,
+            attacker.id,
+            target.id,
+            killed_units
+        );
+    }
+    groups.retain(|g| *g.units.borrow() > 0);
+}
+
+fn find_winning_team(groups: &[Rc<Group>]) -> Option<Team> {
+    if !groups.iter().all(|g| g.team == groups[0].team) {
+        None
+    } else {
+        Some(groups[0].team)
+    }
+}
+
+fn count_units(groups: &[Rc<Group>]) -> usize {
+    groups.iter().map(|g| *g.units.borrow() as usize).sum()
+}
+
+fn simulate_with_immune_boost(groups: &[Group], immune_boost: usize) -> (Option<Team>, usize) {
+    let mut groups = groups.to_vec();
+    for immune_group in groups.iter_mut().filter(|g| g.team == Team::ImmuneSystem) {
+        immune_group.attack_damage += immune_boost as u32;
+    }
+    let mut groups: Vec<_> = groups.iter().cloned().map(Rc::new).collect();
+    simulate_combat(&mut groups);
+    let winning_team = find_winning_team(&groups);
+    let unit_count = count_units(&groups);
+    (winning_team, unit_count)
+}
+
+pub fn solve_part_one(groups: &[Group]) -> usize {
+    let mut groups: Vec<_> = groups.iter().cloned().map(Rc::new).collect();
+    simulate_combat(&mut groups);
+    count_units(&groups)
+}
+
+pub fn solve_part_two(groups: &[Group]) -> usize {
+    let mut lower_limit = 0;
+    let mut upper_limit = 0;
+    loop {
+        let (winning_team, _) = simulate_with_immune_boost(groups, upper_limit);
+        if winning_team.is_some() && winning_team.unwrap() == Team::ImmuneSystem {
+            break;
+        }
+        upper_limit = if upper_limit == 0 { 1 } else { upper_limit * 2 };
+    }
+    while lower_limit <= upper_limit {
+        let boost = (lower_limit + upper_limit) / 2;
+        let (winning_team, _) = simulate_with_immune_boost(groups, boost);
+        if winning_team.is_some() && winning_team.unwrap() == Team::ImmuneSystem {
+            upper_limit = boost - 1;
+        } else {
+            lower_limit = boost + 1;
+        }
+    }
+    let (_, unit_count) = simulate_with_immune_boost(groups, lower_limit);
+    debug!("Boost of {}", lower_limit);
+    unit_count
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input");
+    const SAMPLE_INPUT: &str = include_str!("../sample-input");
+    const PART_ONE_SAMPLE_SOLUTION: usize = 5216;
+    const PART_TWO_SAMPLE_SOLUTION: usize = 51;
+
+    const PART_ONE_SOLUTION: usize = 15470;
+    const PART_TWO_SOLUTION: usize = 5742;
+
+    #[test]
+    fn it_reports_unknown_attack_types() {
+        let input = "Immune System:\n\
+                     17 units each with 5390 hit points (weak to radiation, acid) with an attack \
+                     that does 4507 fire damage at initiative 2";
+        assert_eq!(
+            parse_groups(input).unwrap_err().to_string(),
+            "line 2, column 56: expected an attack type, found \"acid\""
+        );
+    }
+
+    #[test]
+    fn it_solves_part_one_sample_correctly() {
+        let groups = parse_groups(SAMPLE_INPUT).unwrap();
+        assert_eq!(solve_part_one(&groups), PART_ONE_SAMPLE_SOLUTION);
+    }
+    #[test]
+    fn it_solves_part_two_sample_correctly() {
+        let groups = parse_groups(SAMPLE_INPUT).unwrap();
+        assert_eq!(solve_part_two(&groups), PART_TWO_SAMPLE_SOLUTION);
+    }
+
+    #[test]
+    fn it_solves_part_one_correctly() {
+        let groups = parse_groups(INPUT).unwrap();
+        assert_eq!(solve_part_one(&groups), PART_ONE_SOLUTION);
+    }
+    #[test]
+    fn it_solves_part_two_correctly() {
+        let groups = parse_groups(INPUT).unwrap();
+        assert_eq!(solve_part_two(&groups), PART_TWO_SOLUTION);
+    }
+}
@@ -0,0 +1,12 @@
+use bench::bench_solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_25::Day25;
+
+const INPUT: &str = include_str!("../input");
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_solution::<Day25>(c, 25, INPUT);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,130 @@
+use common::{end_of_input, lines, ParseResult, Solution};
+use geometry::{Point, Point4};
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<Point4>;
+    type PartOne = usize;
+    type PartTwo = &'static str;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Self::PartOne {
+        solve_part_one(input)
+    }
+
+    fn part_two(_input: &Self::Input) -> Self::PartTwo {
+        "Merry Christmas!"
+    }
+}
+
+fn find_constellations(points: &[Point4]) -> Vec<Vec<Point4>> {
+    let mut points = points.to_vec();
+    let min_point = *points
+        .iter()
+        .min_by_key(|p| p.coordinates().iter().sum::<i64>())
+        .unwrap();
+    points.sort_by_key(|p| p.manhattan_distance(&min_point));
+    let mut constellations: Vec<Vec<Point4>> = vec![];
+    for &point in points.iter() {
+        let (ref indices, ref mut joinable): (Vec<_>, Vec<_>) = constellations
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, c)| c.iter().any(|p| point.manhattan_distance(p) <= 3))
+            .unzip();
+        if joinable.is_empty() {
+            constellations.push(vec![point]);
+        } else if joinable.len() > 1 {
+            let mut new_constellation = vec![point];
+            new_constellation.extend(joinable.iter().flat_map(|c| c.iter()));
+            for &i in indices.iter().rev() {
+                constellations.remove(i);
+            }
+            constellations.push(new_constellation);
+        } else {
+            joinable[0].push(point);
+        }
+    }
+    constellations
+}
+
+/// Parses a point like `-1,2,2,0` per line
+pub fn parse_input(input: &str) -> ParseResult<Vec<Point4>> {
+    let points: Vec<_> = lines(input)
+        .filter(|line| !line.text.trim().is_empty())
+        .map(|line| {
+            let mut cursor = line.cursor();
+            let mut coordinates = [0; 4];
+            for (i, coordinate) in coordinates.iter_mut().enumerate() {
+                if i > 0 {
+                    cursor.literal(",")?;
+                }
+                cursor.skip_whitespace();
+                *coordinate = cursor.number("a coordinate")?;
+            }
+            cursor.end()?;
+            Ok(Point::new(coordinates))
+        })
+        .collect::<ParseResult<_>>()?;
+    if points.is_empty() {
+        return Err(end_of_input(input, "a point"));
+    }
+    Ok(points)
+}
+
+pub fn solve_part_one(points: &[Point4]) -> usize {
+    let constellations = find_constellations(points);
+    constellations.len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    type Sample = (&'static str, &'static str, usize);
+    const SAMPLE_A: Sample = ("A", include_str!("../sample-a"), 2);
+    const SAMPLE_B: Sample = ("B", include_str!("../sample-b"), 4);
+    const SAMPLE_C: Sample = ("C", include_str!("../sample-c"), 3);
+    const SAMPLE_D: Sample = ("D", include_str!("../sample-d"), 8);
+
+    #[test]
+    fn it_solves_part_one_samples_correctly() {
+        let samples = [SAMPLE_A, SAMPLE_B, SAMPLE_C, SAMPLE_D];
+        for &(name, input, answer) in samples.iter() {
+            let points = parse_input(input).unwrap();
+            assert_eq!(
+                solve_part_one(&points),
+                answer,
+                "failed for sample {}",
+                name
+            );
+        }
+    }
+
+    fn points() -> impl Strategy<Value = Vec<Point4>> {
+        prop::collection::vec(prop::array::uniform4(-10i64..10), 1..50)
+            .prop_map(|points| points.into_iter().map(Point::new).collect())
+    }
+
+    proptest! {
+        #[test]
+        fn it_parses_generated_points(points in points()) {
+            let input: String = points
+                .iter()
+                .map(|p| {
+                    let coordinates: Vec<_> = p.coordinates().iter().map(|c| c.to_string()).collect();
+                    format!("{}\n", coordinates.join(","))
+                })
+                .collect();
+            prop_assert_eq!(parse_input(&input).unwrap(), points.clone());
+            let constellations = find_constellations(&points);
+            let sizes: usize = constellations.iter().map(|c| c.len()).sum();
+            prop_assert_eq!(sizes, points.len());
+        }
+    }
+}